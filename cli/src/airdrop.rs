@@ -0,0 +1,173 @@
+use std::{
+    collections::HashMap, fs::{File, OpenOptions}, io::{BufRead, BufReader, Write}, path::Path, thread,
+    time::Duration
+};
+
+use anyhow::{ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, signature::{Keypair, Signature}, signer::Signer};
+use tatami_client::instructions;
+
+use crate::rpc;
+
+#[derive(Deserialize)]
+struct Row {
+    wallet: String,
+    amount: u64
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Pending,
+    Sent
+}
+
+/// One line of the progress log. A row is written as pending before its
+/// transaction is sent and as sent once it confirms.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    row: usize,
+    wallet: String,
+    amount: u64,
+    signature: String,
+    /// Last block height the transaction can land at.
+    last_valid_block_height: u64,
+    status: Status
+}
+
+struct Progress {
+    file: File,
+    entries: HashMap<usize, Entry>
+}
+
+impl Progress {
+    fn open(path: &Path) -> Result<Self> {
+        let mut entries = HashMap::new();
+
+        if path.exists() {
+            for line in BufReader::new(File::open(path)?).lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                let entry: Entry = serde_json::from_str(&line)
+                    .with_context(|| format!("corrupt progress line: {line}"))?;
+                entries.insert(entry.row, entry);
+            }
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file, entries })
+    }
+
+    fn record(&mut self, entry: Entry) -> Result<()> {
+        writeln!(self.file, "{}", serde_json::to_string(&entry)?)?;
+        self.file.flush()?;
+        self.entries.insert(entry.row, entry);
+        Ok(())
+    }
+
+    /// Whether `row` already landed, checking the chain for rows that were
+    /// sent but never confirmed. A pending transaction can still land until
+    /// its blockhash expires, so this waits that out rather than report it
+    /// missing and have it signed again. Fails if the log recorded a
+    /// different wallet or amount for `row`, since the csv was edited since.
+    fn is_done(&self, client: &RpcClient, row: usize, record: &Row) -> Result<bool> {
+        let Some(entry) = self.entries.get(&row) else {
+            return Ok(false);
+        };
+
+        ensure!(
+            entry.wallet == record.wallet && entry.amount == record.amount,
+            "csv row {} is {} {} but the progress log has {} {}, refusing to resume",
+            row + 1,
+            record.wallet,
+            record.amount,
+            entry.wallet,
+            entry.amount
+        );
+
+        if entry.status == Status::Sent {
+            return Ok(true);
+        }
+
+        let signature: Signature = entry.signature.parse()?;
+
+        while client.get_block_height()? <= entry.last_valid_block_height {
+            if let Some(status) = client.get_signature_status(&signature)? {
+                return Ok(status.is_ok());
+            }
+
+            thread::sleep(Duration::from_secs(2));
+        }
+
+        let status = client.get_signature_status_with_commitment_and_history(&signature, client.commitment(), true)?;
+        Ok(matches!(status, Some(Ok(()))))
+    }
+}
+
+/// Airdrops every `wallet,amount` row of `csv`, skipping rows the progress
+/// log already marks as sent. Amounts are in base units.
+pub fn run(
+    client: &RpcClient,
+    payer: &Keypair,
+    mint: Pubkey,
+    csv_path: &Path,
+    progress_path: &Path,
+    operator: bool,
+    retries: u32
+) -> Result<()> {
+    let mut progress = Progress::open(progress_path)?;
+    let mut reader = csv::Reader::from_path(csv_path)
+        .with_context(|| format!("reading {}", csv_path.display()))?;
+
+    let (mut sent, mut skipped) = (0, 0);
+
+    for (row, record) in reader.deserialize::<Row>().enumerate() {
+        let record = record.with_context(|| format!("csv row {}", row + 1))?;
+        let receiver: Pubkey = record.wallet.parse()
+            .with_context(|| format!("invalid wallet on csv row {}", row + 1))?;
+
+        if progress.is_done(client, row, &record)? {
+            skipped += 1;
+            continue;
+        }
+
+        let ix = if operator {
+            instructions::airdrop_tokens_as_operator(payer.pubkey(), mint, receiver, record.amount)
+        } else {
+            instructions::airdrop_tokens(payer.pubkey(), mint, receiver, record.amount)
+        };
+        let (transaction, last_valid_block_height) = rpc::build_transaction(client, &[ix], &[payer as &dyn Signer])?;
+
+        progress.record(Entry {
+            row,
+            wallet: record.wallet.clone(),
+            amount: record.amount,
+            signature: transaction.signatures[0].to_string(),
+            last_valid_block_height,
+            status: Status::Pending
+        })?;
+
+        let signature = rpc::send_transaction(client, &transaction, retries)
+            .with_context(|| format!("airdrop to {} (csv row {})", record.wallet, row + 1))?;
+
+        progress.record(Entry {
+            row,
+            wallet: record.wallet,
+            amount: record.amount,
+            signature: signature.to_string(),
+            last_valid_block_height,
+            status: Status::Sent
+        })?;
+
+        sent += 1;
+        println!("{receiver}: {} ({signature})", record.amount);
+    }
+
+    println!("airdrop complete: {sent} sent, {skipped} already done");
+    Ok(())
+}
@@ -0,0 +1,998 @@
+mod airdrop;
+mod rpc;
+mod spec;
+
+use std::path::{Path, PathBuf};
+
+use anchor_lang::AccountDeserialize;
+use anyhow::{anyhow, Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig, compute_budget::ComputeBudgetInstruction, hash::hash, program_pack::Pack, pubkey::Pubkey,
+    signature::{read_keypair_file, write_keypair_file, Keypair}, signer::Signer
+};
+use tatami_client::{
+    accounts::{decode_config, decode_project, ConfigAccount, ProjectAccount}, allowlist::AllowlistTree,
+    instructions::{self, CpSwapPool, DaoAddresses, InitializeDaoArgs, LaunchOptions}, pda, AmmKind, AuctionArgs,
+    CreatorRegistry, LpLock, LpLockStatus, ProfileData, ProjectCategory, ProjectIndex, ProjectProfile, SaleArgs,
+    SeedLiquidityArgs, MAX_CREATOR_PROJECTS, PAUSE_AIRDROP, PAUSE_INIT_DAO, PAUSE_INIT_LP, PAUSE_INIT_PROJECT, PAUSE_SALE,
+    ROLE_AIRDROP
+};
+
+#[derive(Parser)]
+#[command(name = "tatami", about = "Operate Tatami launches")]
+struct Cli {
+    /// RPC endpoint
+    #[arg(long, global = true, default_value = "https://api.devnet.solana.com")]
+    url: String,
+    /// Keypair that signs and pays, defaults to the Solana CLI keypair
+    #[arg(long, global = true)]
+    keypair: Option<PathBuf>,
+    /// Times to resend a transaction after a transport failure
+    #[arg(long, global = true, default_value_t = 3)]
+    retries: u32,
+    /// Run admin commands through this approved proposal
+    #[arg(long, global = true)]
+    proposal: Option<u64>,
+    #[command(subcommand)]
+    command: Command
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Manage the platform config
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Run or take part in a presale
+    #[command(subcommand)]
+    Sale(SaleCommand),
+    /// Run or take part in a Dutch auction
+    #[command(subcommand)]
+    Auction(AuctionCommand),
+    /// Lock, burn or release the project's LP tokens
+    #[command(subcommand)]
+    Lp(LpCommand),
+    /// Create a token and project from a launch spec
+    Launch {
+        /// Launch spec, .toml or .json
+        spec: PathBuf,
+        /// Mint keypair to use, a new one is generated and saved if missing
+        #[arg(long)]
+        mint_keypair: Option<PathBuf>
+    },
+    /// Airdrop from a CSV of wallet,amount rows
+    Airdrop {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        csv: PathBuf,
+        /// Progress log, defaults to <csv>.progress.jsonl
+        #[arg(long)]
+        progress: Option<PathBuf>,
+        /// Sign as an airdrop operator instead of the creator
+        #[arg(long)]
+        operator: bool
+    },
+    /// Create the project's DAO
+    InitDao(InitDao),
+    /// Publish the project's profile, replacing any previous one
+    Profile(Profile),
+    /// Hand the project to a new creator, who must accept
+    TransferOwnership {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        to: Pubkey
+    },
+    /// Accept a pending project handover
+    AcceptOwnership {
+        #[arg(long)]
+        mint: Pubkey
+    },
+    /// Let another wallet airdrop for the project, or revoke it with --revoke
+    Operator {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        operator: Pubkey,
+        #[arg(long)]
+        revoke: bool
+    },
+    /// Remove the mint authority for good
+    BurnAuthority {
+        #[arg(long)]
+        mint: Pubkey
+    },
+    /// Show a project's on-chain state
+    Status {
+        #[arg(long)]
+        mint: Pubkey
+    },
+    /// Add a project missing from the registry, e.g. a legacy one
+    Register {
+        #[arg(long)]
+        mint: Pubkey
+    },
+    /// List registered projects, newest first
+    Projects {
+        /// Only projects launched by this wallet
+        #[arg(long)]
+        creator: Option<Pubkey>,
+        #[arg(long, default_value_t = 20)]
+        limit: u64
+    }
+}
+
+#[derive(Subcommand)]
+enum SaleCommand {
+    /// Open a presale of vault tokens, amounts in lamports
+    Create {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Unix timestamp contributions open at
+        #[arg(long)]
+        start: i64,
+        #[arg(long)]
+        end: i64,
+        #[arg(long)]
+        soft_cap: u64,
+        #[arg(long)]
+        hard_cap: u64,
+        #[arg(long, default_value_t = 0)]
+        min: u64,
+        /// Per-wallet total, 0 for no limit
+        #[arg(long, default_value_t = 0)]
+        max: u64,
+        /// Vault tokens for sale, in base units
+        #[arg(long)]
+        tokens: u64,
+        /// File of allowed wallets, one per line
+        #[arg(long)]
+        allowlist: Option<PathBuf>
+    },
+    /// Contribute lamports to a sale
+    Contribute {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// The sale's allowlist file, to build the proof
+        #[arg(long)]
+        allowlist: Option<PathBuf>
+    },
+    /// Claim tokens from a successful sale
+    Claim {
+        #[arg(long)]
+        mint: Pubkey
+    },
+    /// Get a contribution back from a failed sale
+    Refund {
+        #[arg(long)]
+        mint: Pubkey
+    },
+    /// Send the proceeds of a successful sale to the creator
+    Withdraw {
+        #[arg(long)]
+        mint: Pubkey
+    },
+    /// Close a settled sale and release unsold tokens
+    Close {
+        #[arg(long)]
+        mint: Pubkey
+    }
+}
+
+#[derive(Subcommand)]
+enum AuctionCommand {
+    /// Open a descending-price auction of vault tokens, prices in lamports per whole token
+    Create {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Unix timestamp bids open at
+        #[arg(long)]
+        start: i64,
+        #[arg(long)]
+        end: i64,
+        #[arg(long)]
+        start_price: u64,
+        #[arg(long)]
+        floor_price: u64,
+        /// Seconds between price drops
+        #[arg(long, default_value_t = 60)]
+        decay_interval: u64,
+        /// Vault tokens for sale, in base units
+        #[arg(long)]
+        tokens: u64
+    },
+    /// Buy tokens at the current price, in base units
+    Bid {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        quantity: u64
+    },
+    /// Claim won tokens and the refund above the clearing price
+    Claim {
+        #[arg(long)]
+        mint: Pubkey
+    },
+    /// Send the raise to the DAO treasury, or the creator if there is no DAO
+    Withdraw {
+        #[arg(long)]
+        mint: Pubkey
+    },
+    /// Close a settled auction and release unsold tokens
+    Close {
+        #[arg(long)]
+        mint: Pubkey
+    }
+}
+
+#[derive(Subcommand)]
+enum LpCommand {
+    /// Open a Raydium CP-Swap pool with vault tokens and your quote tokens
+    Seed {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Approved CP-Swap program
+        #[arg(long)]
+        amm_program: Pubkey,
+        /// CP-Swap config the pool is created under
+        #[arg(long)]
+        amm_config: Pubkey,
+        /// Receiver of the AMM's pool creation fee
+        #[arg(long)]
+        create_pool_fee: Pubkey,
+        /// Token paired with the project token, deposited from your wallet
+        #[arg(long)]
+        quote_mint: Pubkey,
+        /// Vault tokens to deposit, in base units
+        #[arg(long)]
+        tokens: u64,
+        /// Quote tokens to deposit, in base units
+        #[arg(long)]
+        quote: u64,
+        /// Unix timestamp trading opens at, now if missing
+        #[arg(long, default_value_t = 0)]
+        open_time: u64
+    },
+    /// Lock LP tokens from your wallet, or top up and extend the lock
+    Lock {
+        #[arg(long)]
+        mint: Pubkey,
+        /// LP tokens to add, in base units
+        #[arg(long, default_value_t = 0)]
+        amount: u64,
+        /// Unix timestamp the lock ends at
+        #[arg(long)]
+        unlock_at: i64
+    },
+    /// Never release the locked LP tokens
+    Permanent {
+        #[arg(long)]
+        mint: Pubkey
+    },
+    /// Burn the locked LP tokens
+    Burn {
+        #[arg(long)]
+        mint: Pubkey
+    },
+    /// Return the LP tokens to your wallet once the lock has ended
+    Unlock {
+        #[arg(long)]
+        mint: Pubkey
+    }
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Create the config, signed by the future admin
+    Init {
+        #[arg(long)]
+        fee: u64
+    },
+    /// Change the launch fee
+    Update {
+        #[arg(long)]
+        fee: u64
+    },
+    /// Print the config
+    Show,
+    /// Withdraw collected fees, in lamports or in base units of --mint
+    Withdraw {
+        #[arg(long)]
+        amount: u64,
+        /// Withdraw from this fee mint's vault instead of SOL
+        #[arg(long)]
+        mint: Option<Pubkey>,
+        /// Wallet that receives the fees, defaults to the signer
+        #[arg(long)]
+        destination: Option<Pubkey>
+    },
+    /// Register a partner, or change its share of launch fees
+    Referral {
+        #[arg(long)]
+        referrer: Pubkey,
+        #[arg(long)]
+        share_bps: u16
+    },
+    /// Pause every pausable instruction, or only those given with --only
+    Pause {
+        #[arg(long, value_enum, value_delimiter = ',')]
+        only: Vec<Pausable>
+    },
+    /// Lift every pause
+    Unpause,
+    /// Turn the creator allowlist on or off
+    Allowlist {
+        #[arg(long, action = clap::ArgAction::Set)]
+        enabled: bool,
+        /// Key allowed to grant permits besides the admin
+        #[arg(long)]
+        verifier: Option<Pubkey>
+    },
+    /// Allow a creator to launch while the allowlist is on
+    Permit {
+        #[arg(long)]
+        creator: Pubkey,
+        /// Unix timestamp the permit expires at, never if missing
+        #[arg(long)]
+        expires_at: Option<i64>
+    },
+    /// Revoke a creator's launch permit
+    Revoke {
+        #[arg(long)]
+        creator: Pubkey
+    },
+    /// Replace the admin set, or clear it with no --admin
+    Admins {
+        #[arg(long)]
+        admin: Vec<Pubkey>,
+        #[arg(long, default_value_t = 0)]
+        threshold: u8
+    },
+    /// Let initialize_lp call a Raydium CP-Swap program
+    ApproveAmm {
+        #[arg(long)]
+        program: Pubkey
+    },
+    /// Stop initialize_lp from calling an AMM program
+    RevokeAmm {
+        #[arg(long)]
+        program: Pubkey
+    },
+    /// Approve an admin proposal
+    Approve {
+        #[arg(long)]
+        id: u64
+    },
+    /// Close a proposal you created and reclaim its rent
+    CloseProposal {
+        #[arg(long)]
+        id: u64
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Pausable {
+    Launch,
+    Airdrop,
+    Dao,
+    Lp,
+    Sale
+}
+
+impl Pausable {
+    fn flag(self) -> u8 {
+        match self {
+            Pausable::Launch => PAUSE_INIT_PROJECT,
+            Pausable::Airdrop => PAUSE_AIRDROP,
+            Pausable::Dao => PAUSE_INIT_DAO,
+            Pausable::Lp => PAUSE_INIT_LP,
+            Pausable::Sale => PAUSE_SALE
+        }
+    }
+}
+
+#[derive(Args)]
+struct InitDao {
+    #[arg(long)]
+    mint: Pubkey,
+    #[arg(long)]
+    name: String,
+    /// Tokens minted to the DAO treasury, in base units
+    #[arg(long)]
+    supply: u64,
+    #[arg(long)]
+    min_vote_to_govern: u64,
+    /// Yes vote percentage needed to pass, 1 to 100
+    #[arg(long)]
+    quorum: u8,
+    /// Voting time in seconds
+    #[arg(long)]
+    vote_duration: u32,
+    /// Also create a council mint
+    #[arg(long)]
+    council: bool
+}
+
+#[derive(Args)]
+struct Profile {
+    #[arg(long)]
+    mint: Pubkey,
+    #[arg(long, default_value = "")]
+    website: String,
+    /// Social link, repeat for several
+    #[arg(long = "social")]
+    socials: Vec<String>,
+    /// Off-chain description, only its SHA-256 goes on chain
+    #[arg(long)]
+    description: Option<PathBuf>,
+    #[arg(long, value_enum, default_value_t = Category::Other)]
+    category: Category,
+    /// Launch tag, repeat for several
+    #[arg(long = "tag")]
+    tags: Vec<String>
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Category {
+    Other,
+    Meme,
+    Defi,
+    Gaming,
+    Nft,
+    Infrastructure,
+    Social,
+    Dao
+}
+
+impl From<Category> for ProjectCategory {
+    fn from(category: Category) -> Self {
+        match category {
+            Category::Other => ProjectCategory::Other,
+            Category::Meme => ProjectCategory::Meme,
+            Category::Defi => ProjectCategory::Defi,
+            Category::Gaming => ProjectCategory::Gaming,
+            Category::Nft => ProjectCategory::Nft,
+            Category::Infrastructure => ProjectCategory::Infrastructure,
+            Category::Social => ProjectCategory::Social,
+            Category::Dao => ProjectCategory::Dao
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let client = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+    let payer = load_keypair(cli.keypair.as_deref())?;
+
+    let admin = |ix| match cli.proposal {
+        Some(id) => instructions::with_proposal(ix, id),
+        None => ix
+    };
+
+    match cli.command {
+        Command::Config(ConfigCommand::Init { fee }) => {
+            let ix = instructions::create_config(payer.pubkey(), fee);
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("config created: {signature}");
+        },
+        Command::Config(ConfigCommand::Update { fee }) => {
+            let ix = admin(instructions::update_config(payer.pubkey(), fee));
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("config updated: {signature}");
+        },
+        Command::Config(ConfigCommand::Show) => show_config(&client)?,
+        Command::Config(ConfigCommand::Withdraw { amount, mint, destination }) => {
+            let destination = destination.unwrap_or_else(|| payer.pubkey());
+            let ix = match mint {
+                Some(mint) => {
+                    let destination = anchor_spl::associated_token::get_associated_token_address(&destination, &mint);
+                    instructions::withdraw_token_fees(payer.pubkey(), mint, destination, amount)
+                },
+                None => instructions::withdraw_fees(payer.pubkey(), destination, amount)
+            };
+            let ix = admin(ix);
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("fees withdrawn: {signature}");
+        },
+        Command::Config(ConfigCommand::Referral { referrer, share_bps }) => {
+            let ix = admin(instructions::set_referral(payer.pubkey(), referrer, share_bps));
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("referral set: {signature}");
+        },
+        Command::Config(ConfigCommand::Pause { only }) => {
+            let flags = only.iter().fold(0, |flags, pausable| flags | pausable.flag());
+            let ix = admin(instructions::set_pause(payer.pubkey(), only.is_empty(), flags));
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("paused: {signature}");
+        },
+        Command::Config(ConfigCommand::Unpause) => {
+            let ix = admin(instructions::set_pause(payer.pubkey(), false, 0));
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("unpaused: {signature}");
+        },
+        Command::Config(ConfigCommand::Allowlist { enabled, verifier }) => {
+            let ix = admin(instructions::set_allowlist(payer.pubkey(), enabled, verifier.unwrap_or_default()));
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("allowlist updated: {signature}");
+        },
+        Command::Config(ConfigCommand::Permit { creator, expires_at }) => {
+            let ix = admin(instructions::grant_creator_permit(payer.pubkey(), creator, expires_at.unwrap_or(0)));
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("permit granted: {signature}");
+        },
+        Command::Config(ConfigCommand::Revoke { creator }) => {
+            let ix = admin(instructions::revoke_creator_permit(payer.pubkey(), creator));
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("permit revoked: {signature}");
+        },
+        Command::Config(ConfigCommand::Admins { admin: admins, threshold }) => {
+            let ix = admin(instructions::set_admins(payer.pubkey(), admins, threshold));
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("admins updated: {signature}");
+        },
+        Command::Config(ConfigCommand::ApproveAmm { program }) => {
+            let ix = admin(instructions::approve_amm(payer.pubkey(), program, AmmKind::CpSwap));
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("amm approved: {signature}");
+        },
+        Command::Config(ConfigCommand::RevokeAmm { program }) => {
+            let ix = admin(instructions::revoke_amm(payer.pubkey(), program));
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("amm revoked: {signature}");
+        },
+        Command::Config(ConfigCommand::Approve { id }) => {
+            let ix = instructions::approve_proposal(payer.pubkey(), id);
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("proposal {id} approved: {signature}");
+        },
+        Command::Config(ConfigCommand::CloseProposal { id }) => {
+            let ix = instructions::close_proposal(payer.pubkey(), id);
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("proposal {id} closed: {signature}");
+        },
+        Command::Sale(command) => sale(&client, &payer, command, cli.retries)?,
+        Command::Auction(command) => auction(&client, &payer, command, cli.retries)?,
+        Command::Lp(command) => lp(&client, &payer, command, cli.retries)?,
+        Command::Launch { spec, mint_keypair } => launch(&client, &payer, &spec, mint_keypair, cli.retries)?,
+        Command::Airdrop { mint, csv, progress, operator } => {
+            let progress = progress.unwrap_or_else(|| csv.with_extension("progress.jsonl"));
+            airdrop::run(&client, &payer, mint, &csv, &progress, operator, cli.retries)?;
+        },
+        Command::TransferOwnership { mint, to } => {
+            let ix = instructions::transfer_project_ownership(payer.pubkey(), mint, to);
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("ownership offered to {to}: {signature}");
+        },
+        Command::AcceptOwnership { mint } => {
+            let ix = instructions::accept_project_ownership(payer.pubkey(), mint);
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("ownership accepted: {signature}");
+        },
+        Command::Operator { mint, operator, revoke } => {
+            let ix = if revoke {
+                instructions::remove_project_role(payer.pubkey(), mint, operator)
+            } else {
+                instructions::set_project_role(payer.pubkey(), mint, operator, ROLE_AIRDROP)
+            };
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("operator updated: {signature}");
+        },
+        Command::InitDao(args) => init_dao(&client, &payer, args, cli.retries)?,
+        Command::Profile(args) => {
+            let description_hash = match &args.description {
+                Some(path) => {
+                    let description = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+                    hash(&description).to_bytes()
+                },
+                None => [0; 32]
+            };
+            let data = ProfileData {
+                website: args.website,
+                socials: args.socials,
+                description_hash,
+                category: args.category.into(),
+                tags: args.tags
+            };
+            let ix = instructions::set_project_profile(payer.pubkey(), args.mint, data);
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("profile updated: {signature}");
+        },
+        Command::BurnAuthority { mint } => {
+            let ix = instructions::burn_authority(payer.pubkey(), mint);
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("mint authority burned: {signature}");
+        },
+        Command::Status { mint } => show_project(&client, &mint)?,
+        Command::Register { mint } => {
+            let project = match decode_project(&client.get_account_data(&pda::project(&mint).0)?)? {
+                ProjectAccount::Current(project) => project,
+                ProjectAccount::Legacy(_) | ProjectAccount::Outdated(_) => {
+                    return Err(anyhow!("project needs migrate_project first"))
+                }
+            };
+            let page = registry_page(&client, &project.creator)?;
+            let ix = instructions::register_project(payer.pubkey(), mint, project.creator, project_count(&client)?, page);
+            let signature = rpc::send(&client, &[ix], &[&payer], cli.retries)?;
+            println!("project registered: {signature}");
+        },
+        Command::Projects { creator, limit } => list_projects(&client, creator, limit)?
+    }
+
+    Ok(())
+}
+
+fn load_keypair(path: Option<&Path>) -> Result<Keypair> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let home = std::env::var("HOME").context("HOME is not set, pass --keypair")?;
+            Path::new(&home).join(".config/solana/id.json")
+        }
+    };
+
+    read_keypair_file(&path).map_err(|err| anyhow!("reading keypair {}: {err}", path.display()))
+}
+
+fn launch(client: &RpcClient, payer: &Keypair, spec_path: &Path, mint_keypair: Option<PathBuf>, retries: u32) -> Result<()> {
+    let spec = spec::LaunchSpec::load(spec_path)?;
+
+    let mint = match mint_keypair {
+        Some(path) => read_keypair_file(&path).map_err(|err| anyhow!("reading mint keypair: {err}"))?,
+        None => {
+            let mint = Keypair::new();
+            let path = spec_path.with_file_name(format!("mint-{}.json", mint.pubkey()));
+            write_keypair_file(&mint, &path).map_err(|err| anyhow!("saving mint keypair: {err}"))?;
+            println!("mint keypair saved to {}", path.display());
+            mint
+        }
+    };
+
+    let options = LaunchOptions {
+        fee_mint: spec.fee_mint()?,
+        referrer: spec.referrer()?,
+        creator_permit: client.get_account(&pda::creator_permit(&payer.pubkey()).0).is_ok(),
+        ..LaunchOptions::default()
+    };
+    let earnings = options.referrer
+        .zip(options.fee_mint)
+        .map(|(referrer, fee_mint)| instructions::init_referral_earnings(payer.pubkey(), referrer, fee_mint));
+    let ix = instructions::init_project_with(payer.pubkey(), mint.pubkey(), spec.team_wallet()?, spec.args()?, options);
+    let ixs: Vec<_> = earnings.into_iter().chain([ix]).collect();
+    let signature = rpc::send(client, &ixs, &[payer, &mint], retries)?;
+
+    println!("launched {} ({}): {signature}", spec.name, mint.pubkey());
+    println!("project: {}", pda::project(&mint.pubkey()).0);
+
+    let register = registry_page(client, &payer.pubkey()).and_then(|page| {
+        let ix = instructions::register_project(payer.pubkey(), mint.pubkey(), payer.pubkey(), project_count(client)?, page);
+        rpc::send(client, &[ix], &[payer], retries)
+    });
+    match register {
+        Ok(signature) => println!("project registered: {signature}"),
+        Err(err) => println!("registering failed, retry with `register`: {err}")
+    }
+    Ok(())
+}
+
+/// Registry id the next project gets.
+fn project_count(client: &RpcClient) -> Result<u64> {
+    match decode_config(&client.get_account_data(&pda::config().0)?)? {
+        ConfigAccount::Current(config) => Ok(config.project_count),
+        ConfigAccount::Legacy(_) => Err(anyhow!("config needs migrate_config first"))
+    }
+}
+
+/// Pages of `creator`'s registry list, up to the first missing one.
+fn creator_registry_pages(client: &RpcClient, creator: &Pubkey) -> Result<Vec<CreatorRegistry>> {
+    let mut pages = Vec::new();
+
+    loop {
+        let address = pda::creator_registry(creator, pages.len() as u16).0;
+        let Some(account) = client.get_account_with_commitment(&address, client.commitment())?.value else {
+            return Ok(pages);
+        };
+        pages.push(CreatorRegistry::try_deserialize(&mut &account.data[..])?);
+    }
+}
+
+/// First page of `creator`'s registry list with room for another project.
+fn registry_page(client: &RpcClient, creator: &Pubkey) -> Result<u16> {
+    let pages = creator_registry_pages(client, creator)?;
+    let full = pages.iter().take_while(|page| page.projects.len() == MAX_CREATOR_PROJECTS).count();
+    Ok(full as u16)
+}
+
+fn list_projects(client: &RpcClient, creator: Option<Pubkey>, limit: u64) -> Result<()> {
+    let ids: Vec<u64> = match creator {
+        Some(creator) => creator_registry_pages(client, &creator)?
+            .into_iter()
+            .flat_map(|page| page.projects)
+            .collect(),
+        None => (0..project_count(client)?).collect()
+    };
+
+    for id in ids.into_iter().rev().take(limit as usize) {
+        let data = client.get_account_data(&pda::project_index(id).0)?;
+        let index = ProjectIndex::try_deserialize(&mut &data[..])?;
+        println!("{id}: mint {} project {}", index.mint, index.project);
+    }
+    Ok(())
+}
+
+fn sale(client: &RpcClient, payer: &Keypair, command: SaleCommand, retries: u32) -> Result<()> {
+    let signer = payer.pubkey();
+
+    let ix = match command {
+        SaleCommand::Create { mint, start, end, soft_cap, hard_cap, min, max, tokens, allowlist } => {
+            let merkle_root = match allowlist {
+                Some(path) => AllowlistTree::new(&read_allowlist(&path)?).root(),
+                None => [0; 32]
+            };
+            let args = SaleArgs {
+                start,
+                end,
+                soft_cap,
+                hard_cap,
+                min_contribution: min,
+                max_contribution: max,
+                merkle_root,
+                token_amount: tokens
+            };
+            instructions::create_sale(signer, mint, args)
+        },
+        SaleCommand::Contribute { mint, amount, allowlist } => {
+            let proof = match allowlist {
+                Some(path) => AllowlistTree::new(&read_allowlist(&path)?)
+                    .proof(&signer)
+                    .ok_or_else(|| anyhow!("{signer} is not on the allowlist"))?,
+                None => vec![]
+            };
+            instructions::contribute(signer, mint, amount, proof)
+        },
+        SaleCommand::Claim { mint } => instructions::claim_sale_tokens(signer, mint),
+        SaleCommand::Refund { mint } => instructions::refund_contribution(signer, mint),
+        SaleCommand::Withdraw { mint } => instructions::withdraw_sale_proceeds(signer, mint),
+        SaleCommand::Close { mint } => instructions::close_sale(signer, mint)
+    };
+
+    let signature = rpc::send(client, &[ix], &[payer], retries)?;
+    println!("sale updated: {signature}");
+    Ok(())
+}
+
+fn auction(client: &RpcClient, payer: &Keypair, command: AuctionCommand, retries: u32) -> Result<()> {
+    let signer = payer.pubkey();
+
+    let ix = match command {
+        AuctionCommand::Create { mint, start, end, start_price, floor_price, decay_interval, tokens } => {
+            let args = AuctionArgs { start, end, start_price, floor_price, decay_interval, token_amount: tokens };
+            instructions::create_auction(signer, mint, args)
+        },
+        AuctionCommand::Bid { mint, quantity } => instructions::place_bid(signer, mint, quantity),
+        AuctionCommand::Claim { mint } => instructions::claim_auction_tokens(signer, mint),
+        AuctionCommand::Withdraw { mint } => {
+            let project = match decode_project(&client.get_account_data(&pda::project(&mint).0)?)? {
+                ProjectAccount::Current(project) => project,
+                ProjectAccount::Legacy(_) | ProjectAccount::Outdated(_) => {
+                    return Err(anyhow!("project needs migrate_project first"))
+                }
+            };
+            instructions::withdraw_auction_proceeds(signer, mint, project.proceeds_destination())
+        },
+        AuctionCommand::Close { mint } => instructions::close_auction(signer, mint)
+    };
+
+    let signature = rpc::send(client, &[ix], &[payer], retries)?;
+    println!("auction updated: {signature}");
+    Ok(())
+}
+
+fn lp(client: &RpcClient, payer: &Keypair, command: LpCommand, retries: u32) -> Result<()> {
+    let signer = payer.pubkey();
+    let lp_mint = |mint: &Pubkey| -> Result<Pubkey> {
+        match decode_project(&client.get_account_data(&pda::project(mint).0)?)? {
+            ProjectAccount::Current(project) if project.lp_init => Ok(project.lp_mint),
+            ProjectAccount::Current(_) => Err(anyhow!("liquidity has not been seeded yet")),
+            ProjectAccount::Legacy(_) | ProjectAccount::Outdated(_) => Err(anyhow!("project needs migrate_project first"))
+        }
+    };
+
+    let ix = match command {
+        LpCommand::Seed { mint, amm_program, amm_config, create_pool_fee, quote_mint, tokens, quote, open_time } => {
+            let pool = CpSwapPool::derive(amm_program, amm_config, &mint, &quote_mint);
+            let args = SeedLiquidityArgs { token_amount: tokens, quote_amount: quote, open_time };
+            let ix = instructions::initialize_lp(signer, mint, amm_program, pool.accounts(&signer, create_pool_fee), args);
+            let budget = ComputeBudgetInstruction::set_compute_unit_limit(400_000);
+
+            let signature = rpc::send(client, &[budget, ix], &[payer], retries)?;
+            println!("pool {} seeded, lp mint {}: {signature}", pool.pool_state, pool.lp_mint);
+            return Ok(());
+        },
+        LpCommand::Lock { mint, amount, unlock_at } => {
+            instructions::lock_lp(signer, mint, lp_mint(&mint)?, amount, unlock_at)
+        },
+        LpCommand::Permanent { mint } => instructions::make_lp_lock_permanent(signer, mint),
+        LpCommand::Burn { mint } => instructions::burn_locked_lp(signer, mint, lp_mint(&mint)?),
+        LpCommand::Unlock { mint } => {
+            let lp_mint = lp_mint(&mint)?;
+            let destination = anchor_spl::associated_token::get_associated_token_address(&signer, &lp_mint);
+            instructions::unlock_lp(signer, mint, lp_mint, destination)
+        }
+    };
+
+    let signature = rpc::send(client, &[ix], &[payer], retries)?;
+    println!("lp lock updated: {signature}");
+    Ok(())
+}
+
+fn read_allowlist(path: &Path) -> Result<Vec<Pubkey>> {
+    let raw = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+
+    raw.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse().map_err(|_| anyhow!("invalid wallet {line}")))
+        .collect()
+}
+
+fn init_dao(client: &RpcClient, payer: &Keypair, args: InitDao, retries: u32) -> Result<()> {
+    let council_mint = args.council.then(Keypair::new);
+    let governed_account = Keypair::new().pubkey();
+
+    let dao = DaoAddresses::derive(&args.name, &args.mint, council_mint.as_ref().map(|mint| mint.pubkey()).as_ref(), &governed_account);
+
+    let ix = instructions::initialize_dao(
+        payer.pubkey(),
+        args.mint,
+        council_mint.as_ref().map(|mint| mint.pubkey()),
+        governed_account,
+        InitializeDaoArgs {
+            name: args.name,
+            supply: args.supply,
+            min_vote_to_govern: args.min_vote_to_govern,
+            quorum: args.quorum,
+            vote_duration: args.vote_duration
+        }
+    );
+
+    let mut signers: Vec<&dyn Signer> = vec![payer];
+    if let Some(council_mint) = council_mint.as_ref() {
+        signers.push(council_mint);
+    }
+
+    let signature = rpc::send(client, &[ix], &signers, retries)?;
+
+    println!("dao created: {signature}");
+    println!("realm: {}", dao.realm);
+    println!("governance: {}", dao.governance);
+    println!("native treasury: {}", dao.native_treasury);
+    Ok(())
+}
+
+fn show_config(client: &RpcClient) -> Result<()> {
+    let address = pda::config().0;
+    let data = client.get_account_data(&address)?;
+
+    println!("config: {address}");
+    match decode_config(&data)? {
+        ConfigAccount::Legacy(config) => {
+            println!("version: legacy, run migrate_config");
+            println!("admin: {}", config.creator);
+            println!("fee: {}", config.fee);
+        },
+        ConfigAccount::Current(config) => {
+            println!("version: {}", config.version);
+            println!("admin: {}", config.creator);
+            println!("fee: {}", config.fee);
+            println!("dao fee: {}, lp fee: {}, vesting fee: {}", config.dao_fee, config.lp_fee, config.vesting_fee);
+            println!("paused: {}, pause flags: {:#07b}", config.paused, config.pause_flags);
+            println!("allowlist: {}, verifier: {}", config.allowlist_enabled, config.verifier);
+            for amm in &config.amm_programs[..config.amm_count as usize] {
+                println!("approved amm: {} ({:?})", amm.program, amm.kind);
+            }
+            if config.threshold > 0 {
+                println!("admins ({} of {}):", config.threshold, config.admin_count);
+                for admin in &config.admins[..config.admin_count as usize] {
+                    println!("  {admin}");
+                }
+                println!("proposals created: {}", config.proposal_count);
+            }
+            if config.holder_discount_bps > 0 {
+                println!(
+                    "holder discount: {} bps for holding {} of {}",
+                    config.holder_discount_bps, config.holder_discount_min_balance, config.holder_discount_mint
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn show_project(client: &RpcClient, mint: &Pubkey) -> Result<()> {
+    let address = pda::project(mint).0;
+    let data = client.get_account_data(&address)?;
+
+    println!("project: {address}");
+    let project = match decode_project(&data)? {
+        ProjectAccount::Legacy(project) => {
+            println!("version: legacy, run migrate_project");
+            println!("creator: {}", project.creator);
+            println!("recipients paid: {}/{}", project.recipients_paid, project.recipients);
+            return Ok(());
+        },
+        ProjectAccount::Outdated(project) => {
+            println!("version: {}, run migrate_project", project.version);
+            println!("creator: {}", project.creator);
+            println!("recipients paid: {}/{}", project.recipients_paid, project.recipients);
+            return Ok(());
+        },
+        ProjectAccount::Current(project) => project
+    };
+
+    let mint_data = client.get_account_data(mint)?;
+    let decimals = anchor_spl::token::spl_token::state::Mint::unpack(&mint_data)?.decimals;
+    let vault_balance = client
+        .get_token_account_balance(&pda::vault_token_account(mint))
+        .map(|balance| balance.ui_amount_string)
+        .unwrap_or_else(|_| "closed".to_string());
+
+    println!("version: {}", project.version);
+    if project.registered {
+        println!("registry id: {}", project.id);
+    } else {
+        println!("registry id: none, run register");
+    }
+    println!("state: {:?}", project.state);
+    println!("creator: {}", project.creator);
+    if project.pending_creator != Pubkey::default() {
+        println!("pending creator: {}", project.pending_creator);
+    }
+    println!("mint: {} ({decimals} decimals)", project.mint);
+    println!("mint authority: {}", if project.mint_exist { "active" } else { "burned" });
+    println!("recipients paid: {}/{}", project.recipients_paid, project.recipients);
+    println!("vault balance: {vault_balance}");
+    if project.reserved_tokens > 0 {
+        println!("reserved for sale: {} base units", project.reserved_tokens);
+    }
+    println!("dao: {}", if project.dao_init { project.treasury.to_string() } else { "none".to_string() });
+    println!("dao allocation cap: {}", project.dao_allocation);
+    println!("airdrop deadline: {}", project.airdrop_deadline);
+    println!("clawback policy: {:?}", project.clawback_policy);
+    println!("features: {:#05b}", project.features);
+    if project.lp_init {
+        println!("lp: pool {}, lp mint {}", project.pool, project.lp_mint);
+    } else {
+        println!("lp: none");
+    }
+    match project.lp_lock {
+        LpLockStatus::Unlocked => println!("lp lock: none"),
+        status => {
+            let data = client.get_account_data(&pda::lp_lock(&address).0)?;
+            let lock = LpLock::try_deserialize(&mut &data[..])?;
+            println!("lp lock: {status:?}, {} of {} until {}", lock.amount, lock.lp_mint, lock.unlock_at);
+        }
+    }
+    if project.fee_mint == Pubkey::default() {
+        println!("fee paid: {} lamports", project.fee_paid);
+    } else {
+        println!("fee paid: {} of {}", project.fee_paid, project.fee_mint);
+    }
+    println!("airdrop closed: {}", project.airdrop_closed);
+    println!("swept: {}, clawed back: {}, burned: {}", project.swept, project.clawed_back, project.burned);
+
+    if let Ok(data) = client.get_account_data(&pda::project_profile(&address).0) {
+        let profile = ProjectProfile::try_deserialize(&mut &data[..])?;
+        println!("category: {:?}", profile.category);
+        println!("website: {}", profile.website);
+        for social in &profile.socials {
+            println!("  {social}");
+        }
+        println!("tags: {}", profile.tags.join(", "));
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use std::{thread, time::Duration};
+
+use anyhow::{anyhow, Result};
+use solana_client::{client_error::ClientError, rpc_client::RpcClient};
+use solana_sdk::{
+    instruction::Instruction, signature::Signature, signer::Signer, transaction::{Transaction, TransactionError}
+};
+use tatami_client::errors::decode_instruction_error;
+
+/// Signs `instructions` with a fresh blockhash. The first signer pays. Also
+/// returns the last block height the transaction can land at.
+pub fn build_transaction(
+    client: &RpcClient,
+    instructions: &[Instruction],
+    signers: &[&dyn Signer]
+) -> Result<(Transaction, u64)> {
+    let (blockhash, last_valid_block_height) = client.get_latest_blockhash_with_commitment(client.commitment())?;
+    let payer = signers[0].pubkey();
+
+    Ok((Transaction::new_signed_with_payer(instructions, Some(&payer), signers, blockhash), last_valid_block_height))
+}
+
+/// Sends a signed transaction, retrying transport failures. Program errors
+/// are returned straight away since resending can't fix them.
+pub fn send_transaction(client: &RpcClient, transaction: &Transaction, retries: u32) -> Result<Signature> {
+    let mut attempt = 0;
+
+    loop {
+        match client.send_and_confirm_transaction(transaction) {
+            Ok(signature) => return Ok(signature),
+            Err(err) => {
+                if let Some(program_error) = program_error(&err) {
+                    return Err(program_error);
+                }
+
+                if attempt >= retries {
+                    return Err(err.into());
+                }
+
+                attempt += 1;
+                eprintln!("send failed ({err}), retry {attempt}/{retries}");
+                thread::sleep(Duration::from_secs(2u64.pow(attempt.min(5))));
+            }
+        }
+    }
+}
+
+/// Builds, signs and sends `instructions`.
+pub fn send(client: &RpcClient, instructions: &[Instruction], signers: &[&dyn Signer], retries: u32) -> Result<Signature> {
+    let (transaction, _) = build_transaction(client, instructions, signers)?;
+    send_transaction(client, &transaction, retries)
+}
+
+fn program_error(err: &ClientError) -> Option<anyhow::Error> {
+    match err.get_transaction_error()? {
+        TransactionError::InstructionError(index, ix_error) => {
+            let decoded = decode_instruction_error(&ix_error)
+                .map(|error| format!("{error:?}: {error}"))
+                .unwrap_or_else(|| ix_error.to_string());
+
+            Some(anyhow!("instruction {index} failed: {decoded}"))
+        },
+        other => Some(anyhow!("transaction failed: {other}"))
+    }
+}
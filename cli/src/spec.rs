@@ -0,0 +1,145 @@
+use std::{fs, path::Path};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use tatami_client::{instructions::InitProjectArgs, ClawbackPolicy, CreatorShare, FEATURE_DAO, FEATURE_LP, FEATURE_VESTING};
+
+/// Launch description read from a `.toml` or `.json` file.
+#[derive(Deserialize)]
+pub struct LaunchSpec {
+    pub name: String,
+    pub symbol: String,
+    #[serde(default)]
+    pub uri: String,
+    pub decimals: u8,
+    pub recipients: u16,
+    /// Base units minted to the team wallet, ignored without `team_wallet`.
+    #[serde(default)]
+    pub team_supply: u64,
+    /// Base units minted to the vault for the airdrop.
+    pub airdrop_supply: u64,
+    pub team_wallet: Option<String>,
+    #[serde(default)]
+    pub dao_allocation: u64,
+    /// Unix timestamp after which the airdrop can be closed.
+    #[serde(default)]
+    pub airdrop_deadline: i64,
+    #[serde(default)]
+    pub clawback_policy: ClawbackPolicySpec,
+    /// Optional features to pay for at launch.
+    #[serde(default)]
+    pub features: Vec<FeatureSpec>,
+    /// Pay the launch fee in this token instead of SOL.
+    pub fee_mint: Option<String>,
+    /// Partner that referred this launch.
+    pub referrer: Option<String>,
+    /// Metadata creators, the signer alone if empty.
+    #[serde(default)]
+    pub creators: Vec<CreatorSpec>,
+    /// Collection to list in the metadata, unverified.
+    pub collection: Option<String>
+}
+
+#[derive(Deserialize)]
+pub struct CreatorSpec {
+    pub address: String,
+    /// Percent of royalties, all shares must sum to 100.
+    pub share: u8
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum FeatureSpec {
+    Dao,
+    Lp,
+    Vesting
+}
+
+impl FeatureSpec {
+    fn flag(self) -> u8 {
+        match self {
+            FeatureSpec::Dao => FEATURE_DAO,
+            FeatureSpec::Lp => FEATURE_LP,
+            FeatureSpec::Vesting => FEATURE_VESTING
+        }
+    }
+}
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ClawbackPolicySpec {
+    #[default]
+    Creator,
+    Treasury,
+    Burn
+}
+
+impl From<ClawbackPolicySpec> for ClawbackPolicy {
+    fn from(policy: ClawbackPolicySpec) -> Self {
+        match policy {
+            ClawbackPolicySpec::Creator => ClawbackPolicy::Creator,
+            ClawbackPolicySpec::Treasury => ClawbackPolicy::Treasury,
+            ClawbackPolicySpec::Burn => ClawbackPolicy::Burn
+        }
+    }
+}
+
+impl LaunchSpec {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+
+        let spec: Self = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&raw)?,
+            Some("json") => serde_json::from_str(&raw)?,
+            _ => bail!("launch spec must be a .toml or .json file")
+        };
+
+        Ok(spec)
+    }
+
+    pub fn team_wallet(&self) -> Result<Option<Pubkey>> {
+        self.team_wallet.as_deref()
+            .map(|wallet| wallet.parse().context("invalid team_wallet"))
+            .transpose()
+    }
+
+    pub fn fee_mint(&self) -> Result<Option<Pubkey>> {
+        self.fee_mint.as_deref()
+            .map(|mint| mint.parse().context("invalid fee_mint"))
+            .transpose()
+    }
+
+    pub fn referrer(&self) -> Result<Option<Pubkey>> {
+        self.referrer.as_deref()
+            .map(|referrer| referrer.parse().context("invalid referrer"))
+            .transpose()
+    }
+
+    pub fn args(&self) -> Result<InitProjectArgs> {
+        let creators = self.creators.iter()
+            .map(|creator| Ok(CreatorShare {
+                address: creator.address.parse().context("invalid creator address")?,
+                share: creator.share
+            }))
+            .collect::<Result<_>>()?;
+        let collection = self.collection.as_deref()
+            .map(|collection| collection.parse().context("invalid collection"))
+            .transpose()?;
+
+        Ok(InitProjectArgs {
+            decimals: self.decimals,
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            uri: self.uri.clone(),
+            recipients: self.recipients,
+            supply: [self.team_supply, self.airdrop_supply],
+            dao_allocation: self.dao_allocation,
+            airdrop_deadline: self.airdrop_deadline,
+            clawback_policy: self.clawback_policy.into(),
+            features: self.features.iter().fold(0, |flags, feature| flags | feature.flag()),
+            creators,
+            collection
+        })
+    }
+}
@@ -0,0 +1,95 @@
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator, Space};
+use tatami_v2::{Config, ConfigV0, Project, ProjectV0, LEGACY_CONFIG_LEN, LEGACY_PROJECT_LEN};
+
+/// A Project account as found on chain. Legacy and outdated accounts need
+/// `migrate_project` before the program accepts them again.
+pub enum ProjectAccount {
+    Legacy(ProjectV0),
+    /// Versioned, but from before the last size bump. Fields it predates
+    /// read as zeroes.
+    Outdated(Box<Project>),
+    Current(Box<Project>)
+}
+
+/// A Config account as found on chain. Legacy accounts need
+/// `migrate_config` before the program accepts them again.
+pub enum ConfigAccount {
+    Legacy(ConfigV0),
+    Current(Box<Config>)
+}
+
+pub fn decode_project(data: &[u8]) -> anchor_lang::Result<ProjectAccount> {
+    if data.len() == LEGACY_PROJECT_LEN && data[..8] == Project::DISCRIMINATOR {
+        let legacy = ProjectV0::deserialize(&mut &data[8..])?;
+        return Ok(ProjectAccount::Legacy(legacy));
+    }
+
+    if data.len() < 8 + Project::INIT_SPACE {
+        return Ok(ProjectAccount::Outdated(Box::new(Project::try_deserialize_padded(data)?)));
+    }
+
+    Ok(ProjectAccount::Current(Box::new(Project::try_deserialize(&mut &data[..])?)))
+}
+
+pub fn decode_config(data: &[u8]) -> anchor_lang::Result<ConfigAccount> {
+    if data.len() == LEGACY_CONFIG_LEN && data[..8] == Config::DISCRIMINATOR {
+        let legacy = ConfigV0::deserialize(&mut &data[8..])?;
+        return Ok(ConfigAccount::Legacy(legacy));
+    }
+
+    Ok(ConfigAccount::Current(Box::new(Config::try_deserialize(&mut &data[..])?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{prelude::Pubkey, AccountSerialize, AnchorSerialize};
+
+    use super::*;
+
+    fn project_data() -> Vec<u8> {
+        let project = Project::from_legacy(ProjectV0 {
+            creator: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            dao_init: false,
+            lp_init: false,
+            mint_exist: true,
+            bump: 255,
+            recipients: 10,
+            recipients_paid: 3
+        });
+
+        let mut data = Vec::new();
+        project.try_serialize(&mut data).unwrap();
+        data.resize(8 + Project::INIT_SPACE, 0);
+        data
+    }
+
+    #[test]
+    fn decodes_every_project_layout() {
+        let data = project_data();
+        assert!(matches!(decode_project(&data), Ok(ProjectAccount::Current(_))));
+
+        // Version 1 ended at `lp_lock`, followed by 5 reserved bytes.
+        let v1 = &data[..8 + Project::INIT_SPACE - 2 * 32 - 64 + 5];
+        match decode_project(v1) {
+            Ok(ProjectAccount::Outdated(project)) => {
+                assert_eq!(project.recipients_paid, 3);
+                assert_eq!(project.lp_mint, Pubkey::default());
+            },
+            _ => panic!("v1 layout should decode as outdated")
+        }
+
+        let mut legacy = Project::DISCRIMINATOR.to_vec();
+        ProjectV0 {
+            creator: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            dao_init: false,
+            lp_init: false,
+            mint_exist: true,
+            bump: 255,
+            recipients: 10,
+            recipients_paid: 0
+        }.serialize(&mut legacy).unwrap();
+        assert!(matches!(decode_project(&legacy), Ok(ProjectAccount::Legacy(_))));
+    }
+}
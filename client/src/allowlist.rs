@@ -0,0 +1,83 @@
+use anchor_lang::prelude::Pubkey;
+use solana_program::keccak;
+use tatami_v2::allowlist_leaf;
+
+/// Merkle tree over a sale allowlist, hashed the way the program checks
+/// proofs: pairs in sorted order, an odd node carried up unchanged.
+pub struct AllowlistTree {
+    wallets: Vec<Pubkey>,
+    levels: Vec<Vec<[u8; 32]>>
+}
+
+impl AllowlistTree {
+    pub fn new(wallets: &[Pubkey]) -> Self {
+        let mut levels = vec![wallets.iter().map(allowlist_leaf).collect::<Vec<_>>()];
+
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    [node] => *node,
+                    _ => unreachable!()
+                })
+                .collect();
+
+            levels.push(next);
+        }
+
+        Self { wallets: wallets.to_vec(), levels }
+    }
+
+    /// Zeroed for an empty list, which makes the sale open to anyone.
+    pub fn root(&self) -> [u8; 32] {
+        self.levels[self.levels.len() - 1].first().copied().unwrap_or([0; 32])
+    }
+
+    /// Proof to pass to `contribute`, `None` if `wallet` isn't listed.
+    pub fn proof(&self, wallet: &Pubkey) -> Option<Vec<[u8; 32]>> {
+        let mut index = self.wallets.iter().position(|listed| listed == wallet)?;
+        let mut proof = Vec::new();
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        Some(proof)
+    }
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    if left <= right {
+        keccak::hashv(&[left, right]).0
+    } else {
+        keccak::hashv(&[right, left]).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tatami_v2::verify_allowlist_proof;
+
+    use super::*;
+
+    #[test]
+    fn proofs_verify_against_the_root() {
+        for size in 1..=7 {
+            let wallets: Vec<Pubkey> = (0..size).map(|_| Pubkey::new_unique()).collect();
+            let tree = AllowlistTree::new(&wallets);
+
+            for wallet in &wallets {
+                let proof = tree.proof(wallet).unwrap();
+                assert!(verify_allowlist_proof(&proof, &tree.root(), wallet));
+            }
+
+            let stranger = Pubkey::new_unique();
+            assert!(tree.proof(&stranger).is_none());
+            assert!(!verify_allowlist_proof(&tree.proof(&wallets[0]).unwrap(), &tree.root(), &stranger));
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use solana_program::instruction::InstructionError;
+use tatami_v2::Errors;
+
+/// Every program error, in declaration order. Keep in sync with `Errors`.
+pub const ALL_ERRORS: &[Errors] = &[
+    Errors::NoCouncilTokenHolding,
+    Errors::InvalidQuorum,
+    Errors::MaxRecipientsPaid,
+    Errors::InvalidStateTransition,
+    Errors::DaoAllocationExceeded,
+    Errors::DaoNotInitialized,
+    Errors::VaultEmpty,
+    Errors::InvalidDeadline,
+    Errors::AirdropDeadlineSet,
+    Errors::NoAirdropDeadline,
+    Errors::AirdropEnded,
+    Errors::AirdropNotEnded,
+    Errors::AirdropClosed,
+    Errors::MissingClawbackAccount,
+    Errors::Unauthorized,
+    Errors::InvalidAmount,
+    Errors::InsufficientVaultBalance,
+    Errors::VaultNotEmpty,
+    Errors::MintAuthorityNotBurned,
+    Errors::InvalidAccountVersion,
+    Errors::AlreadyMigrated,
+    Errors::FeatureNotPaid,
+    Errors::InvalidBasisPoints,
+    Errors::DiscountNotEligible,
+    Errors::FeeOverflow,
+    Errors::FeeMintDisabled,
+    Errors::MissingFeeAccount,
+    Errors::InvalidFeeAccount,
+    Errors::MissingReferrerAccount,
+    Errors::InvalidReferrer,
+    Errors::Paused,
+    Errors::CreatorNotAllowed,
+    Errors::PermitExpired,
+    Errors::InvalidAttestation,
+    Errors::InvalidAdminSet,
+    Errors::ProposalRequired,
+    Errors::ProposalExecuted,
+    Errors::ProposalStale,
+    Errors::ProposalNotApproved,
+    Errors::ProposalMismatch,
+    Errors::InvalidNewCreator,
+    Errors::InvalidCreators,
+    Errors::AlreadyRegistered,
+    Errors::TooManyProfileEntries,
+    Errors::InvalidProfileField,
+    Errors::InvalidSaleConfig,
+    Errors::SaleNotActive,
+    Errors::SaleNotEnded,
+    Errors::NotOnAllowlist,
+    Errors::InvalidContribution,
+    Errors::SaleHardCapReached,
+    Errors::SoftCapNotMet,
+    Errors::SoftCapMet,
+    Errors::SaleProceedsWithdrawn,
+    Errors::SaleNotSettled,
+    Errors::InvalidAuctionConfig,
+    Errors::AuctionNotActive,
+    Errors::AuctionNotEnded,
+    Errors::AuctionProceedsWithdrawn,
+    Errors::AuctionNotSettled,
+    Errors::InvalidProceedsDestination,
+    Errors::InvalidUnlockTime,
+    Errors::InvalidLpMint,
+    Errors::LpNotLocked,
+    Errors::LpLockPermanent,
+    Errors::LpStillLocked,
+    Errors::AmmNotApproved,
+    Errors::TooManyAmmPrograms,
+    Errors::InvalidAmmAccounts,
+    Errors::LpAlreadyInitialized,
+    Errors::CreatorRegistryFull,
+    Errors::ProfileNotClosed,
+    Errors::SaleNotClosed,
+    Errors::MathOverflow,
+    Errors::InvalidDecimals,
+    Errors::AuctionNotClosed,
+    Errors::LpLockOpen,
+    Errors::LpNotInitialized,
+    Errors::ProjectFinalized,
+    Errors::InvalidRegistryPage
+];
+
+/// Maps a custom program error code back to the `Errors` variant.
+pub fn decode_error(code: u32) -> Option<Errors> {
+    let index = code.checked_sub(ERROR_CODE_OFFSET)? as usize;
+    ALL_ERRORS.get(index).copied()
+}
+
+/// Extracts the `Errors` variant from a failed instruction, if it came from
+/// this program.
+pub fn decode_instruction_error(error: &InstructionError) -> Option<Errors> {
+    match error {
+        InstructionError::Custom(code) => decode_error(*code),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip() {
+        for error in ALL_ERRORS {
+            let code: u32 = (*error).into();
+            assert_eq!(decode_error(code).map(u32::from), Some(code));
+        }
+    }
+
+    #[test]
+    fn all_errors_match_program_enum() {
+        let source = include_str!("../../programs/tatami-v2/src/lib.rs");
+        let body = source
+            .split_once("pub enum Errors {")
+            .and_then(|(_, rest)| rest.split_once("\n}"))
+            .expect("Errors enum in program source")
+            .0;
+
+        let variants: Vec<&str> = body
+            .lines()
+            .map(|line| line.trim().trim_end_matches(','))
+            .filter(|line| !line.is_empty() && !line.starts_with("#[") && !line.starts_with("//"))
+            .collect();
+        let listed: Vec<String> = ALL_ERRORS.iter().map(|error| error.name()).collect();
+
+        assert_eq!(listed, variants);
+    }
+
+    #[test]
+    fn unknown_codes_are_rejected() {
+        assert!(decode_error(0).is_none());
+        assert!(decode_error(ERROR_CODE_OFFSET + ALL_ERRORS.len() as u32).is_none());
+    }
+}
@@ -0,0 +1,1124 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::{associated_token::get_associated_token_address, metadata::mpl_token_metadata};
+use solana_program::{ed25519_program, instruction::{AccountMeta, Instruction}, system_program, sysvar};
+use tatami_v2::{
+    accounts, instruction, AdminAction, AmmKind, AuctionArgs, ClawbackPolicy, FeeSchedule, ProfileData, Project,
+    SaleArgs, SeedLiquidityArgs, REALMS_ID
+};
+
+pub use tatami_v2::InitProjectArgs;
+
+use crate::pda;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: tatami_v2::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data()
+    }
+}
+
+pub fn create_config(signer: Pubkey, fee: u64) -> Instruction {
+    build(
+        accounts::CreateConfig {
+            config: pda::config().0,
+            signer,
+            system_program: system_program::ID
+        },
+        instruction::CreateConfig { fee }
+    )
+}
+
+pub fn update_config(signer: Pubkey, fee: u64) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::config().0,
+            proposal: None,
+            signer
+        },
+        instruction::UpdateConfig { fee }
+    )
+}
+
+pub fn set_fee_schedule(signer: Pubkey, schedule: FeeSchedule) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::config().0,
+            proposal: None,
+            signer
+        },
+        instruction::SetFeeSchedule { schedule }
+    )
+}
+
+/// With no admins the config creator acts alone again.
+pub fn set_admins(signer: Pubkey, admins: Vec<Pubkey>, threshold: u8) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::config().0,
+            proposal: None,
+            signer
+        },
+        instruction::SetAdmins { admins, threshold }
+    )
+}
+
+/// `id` must be the config's current `proposal_count`.
+pub fn create_proposal(signer: Pubkey, id: u64, action: AdminAction) -> Instruction {
+    build(
+        accounts::CreateProposal {
+            config: pda::config().0,
+            proposal: pda::proposal(id).0,
+            signer,
+            system_program: system_program::ID
+        },
+        instruction::CreateProposal { action }
+    )
+}
+
+pub fn approve_proposal(signer: Pubkey, id: u64) -> Instruction {
+    build(
+        accounts::ApproveProposal {
+            config: pda::config().0,
+            proposal: pda::proposal(id).0,
+            signer
+        },
+        instruction::ApproveProposal {}
+    )
+}
+
+/// Only the proposer may close a proposal, executed or not.
+pub fn close_proposal(signer: Pubkey, id: u64) -> Instruction {
+    build(
+        accounts::CloseProposal {
+            proposal: pda::proposal(id).0,
+            signer
+        },
+        instruction::CloseProposal {}
+    )
+}
+
+/// Runs an admin instruction through approved proposal `id`, which is needed
+/// once the config has an admin set. The proposal is the only optional
+/// account of admin instructions, so it replaces the placeholder.
+pub fn with_proposal(mut ix: Instruction, id: u64) -> Instruction {
+    if let Some(meta) = ix.accounts.iter_mut().find(|meta| meta.pubkey == tatami_v2::ID) {
+        *meta = AccountMeta::new(pda::proposal(id).0, false);
+    }
+    ix
+}
+
+/// `pause_flags` is a mask of `PAUSE_*` bits, applied on top of `paused`.
+pub fn set_pause(signer: Pubkey, paused: bool, pause_flags: u8) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::config().0,
+            proposal: None,
+            signer
+        },
+        instruction::SetPause { paused, pause_flags }
+    )
+}
+
+pub fn set_fee_override(signer: Pubkey, creator: Pubkey, discount_bps: u16, waived: bool) -> Instruction {
+    build(
+        accounts::SetFeeOverride {
+            config: pda::config().0,
+            proposal: None,
+            fee_override: pda::fee_override(&creator).0,
+            signer,
+            system_program: system_program::ID
+        },
+        instruction::SetFeeOverride { creator, discount_bps, waived }
+    )
+}
+
+pub fn remove_fee_override(signer: Pubkey, creator: Pubkey) -> Instruction {
+    build(
+        accounts::RemoveFeeOverride {
+            config: pda::config().0,
+            proposal: None,
+            fee_override: pda::fee_override(&creator).0,
+            signer
+        },
+        instruction::RemoveFeeOverride { creator }
+    )
+}
+
+/// `verifier` may grant and revoke permits besides the admin, and sign
+/// attestations for `claim_creator_permit`. Pass the default key for none.
+pub fn set_allowlist(signer: Pubkey, enabled: bool, verifier: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::config().0,
+            proposal: None,
+            signer
+        },
+        instruction::SetAllowlist { enabled, verifier }
+    )
+}
+
+pub fn approve_amm(signer: Pubkey, program: Pubkey, kind: AmmKind) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::config().0,
+            proposal: None,
+            signer
+        },
+        instruction::ApproveAmm { program, kind }
+    )
+}
+
+pub fn revoke_amm(signer: Pubkey, program: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::config().0,
+            proposal: None,
+            signer
+        },
+        instruction::RevokeAmm { program }
+    )
+}
+
+/// `expires_at` is a unix timestamp, 0 for a permit that never expires.
+pub fn grant_creator_permit(signer: Pubkey, creator: Pubkey, expires_at: i64) -> Instruction {
+    build(
+        accounts::GrantCreatorPermit {
+            config: pda::config().0,
+            proposal: None,
+            permit: pda::creator_permit(&creator).0,
+            signer,
+            system_program: system_program::ID
+        },
+        instruction::GrantCreatorPermit { creator, expires_at }
+    )
+}
+
+/// Must directly follow `verify_signature` of the verifier's `signature`
+/// over `tatami_v2::permit_message(&signer, nonce, expires_at)`, `nonce`
+/// being the signer's permit nonce, 0 before they have one.
+pub fn claim_creator_permit(signer: Pubkey, expires_at: i64) -> Instruction {
+    build(
+        accounts::ClaimCreatorPermit {
+            config: pda::config().0,
+            permit: pda::creator_permit(&signer).0,
+            signer,
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID
+        },
+        instruction::ClaimCreatorPermit { expires_at }
+    )
+}
+
+pub fn revoke_creator_permit(signer: Pubkey, creator: Pubkey) -> Instruction {
+    build(
+        accounts::RevokeCreatorPermit {
+            config: pda::config().0,
+            proposal: None,
+            permit: pda::creator_permit(&creator).0,
+            signer
+        },
+        instruction::RevokeCreatorPermit { creator }
+    )
+}
+
+/// Ed25519 program instruction checking `signature` by `signer` over
+/// `message`, with everything stored inline.
+pub fn verify_signature(signer: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Instruction {
+    const KEY_OFFSET: u16 = 16;
+    const SIGNATURE_OFFSET: u16 = KEY_OFFSET + 32;
+    const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 64;
+    const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+    let offsets = [
+        SIGNATURE_OFFSET,
+        CURRENT_INSTRUCTION,
+        KEY_OFFSET,
+        CURRENT_INSTRUCTION,
+        MESSAGE_OFFSET,
+        message.len() as u16,
+        CURRENT_INSTRUCTION
+    ];
+
+    let mut data = vec![1, 0];
+    data.extend(offsets.iter().flat_map(|offset| offset.to_le_bytes()));
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data
+    }
+}
+
+pub fn set_referral(signer: Pubkey, referrer: Pubkey, share_bps: u16) -> Instruction {
+    build(
+        accounts::SetReferral {
+            config: pda::config().0,
+            proposal: None,
+            referral: pda::referral(&referrer).0,
+            signer,
+            system_program: system_program::ID
+        },
+        instruction::SetReferral { referrer, share_bps }
+    )
+}
+
+pub fn remove_referral(signer: Pubkey, referrer: Pubkey) -> Instruction {
+    build(
+        accounts::RemoveReferral {
+            config: pda::config().0,
+            proposal: None,
+            referral: pda::referral(&referrer).0,
+            signer
+        },
+        instruction::RemoveReferral { referrer }
+    )
+}
+
+/// Needed before launches that pay `referrer` in `fee_mint`. Safe to send
+/// again once it exists.
+pub fn init_referral_earnings(signer: Pubkey, referrer: Pubkey, fee_mint: Pubkey) -> Instruction {
+    build(
+        accounts::InitReferralEarnings {
+            referral: pda::referral(&referrer).0,
+            fee_mint: pda::fee_mint(&fee_mint).0,
+            referral_earnings: pda::referral_earnings(&referrer, &fee_mint).0,
+            signer,
+            system_program: system_program::ID
+        },
+        instruction::InitReferralEarnings {}
+    )
+}
+
+/// `price` is the fee in `mint` base units per SOL.
+pub fn add_fee_mint(signer: Pubkey, mint: Pubkey, price: u64) -> Instruction {
+    build(
+        accounts::AddFeeMint {
+            config: pda::config().0,
+            proposal: None,
+            fee_mint: pda::fee_mint(&mint).0,
+            mint,
+            fee_vault: pda::fee_vault(&mint),
+            signer,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID
+        },
+        instruction::AddFeeMint { price }
+    )
+}
+
+pub fn update_fee_mint(signer: Pubkey, mint: Pubkey, price: u64, enabled: bool) -> Instruction {
+    build(
+        accounts::UpdateFeeMint {
+            config: pda::config().0,
+            proposal: None,
+            fee_mint: pda::fee_mint(&mint).0,
+            signer
+        },
+        instruction::UpdateFeeMint { price, enabled }
+    )
+}
+
+pub fn withdraw_fees(signer: Pubkey, destination: Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::WithdrawFees {
+            config: pda::config().0,
+            proposal: None,
+            vault: pda::vault().0,
+            destination,
+            signer,
+            system_program: system_program::ID
+        },
+        instruction::WithdrawFees { amount }
+    )
+}
+
+/// `destination` must be a token account for `mint`.
+pub fn withdraw_token_fees(signer: Pubkey, mint: Pubkey, destination: Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::WithdrawTokenFees {
+            config: pda::config().0,
+            proposal: None,
+            mint,
+            fee_vault: pda::fee_vault(&mint),
+            destination,
+            signer,
+            token_program: anchor_spl::token::ID
+        },
+        instruction::WithdrawTokenFees { amount }
+    )
+}
+
+/// Optional accounts for `init_project`.
+#[derive(Default)]
+pub struct LaunchOptions {
+    /// Pass the signer's fee override PDA.
+    pub fee_override: bool,
+    /// Signer's token account used for the holder discount.
+    pub discount_token_account: Option<Pubkey>,
+    /// Pay the fee in this token, from the signer's associated token account.
+    pub fee_mint: Option<Pubkey>,
+    /// Partner that referred the launch. Token fee shares go to their
+    /// associated token account for `fee_mint`, and need
+    /// `init_referral_earnings` sent first.
+    pub referrer: Option<Pubkey>,
+    /// Pass the signer's creator permit, needed while the allowlist is on.
+    pub creator_permit: bool
+}
+
+/// `mint` must also sign the transaction since the program creates it.
+/// The project is added to the registry by a separate `register_project`.
+pub fn init_project(
+    signer: Pubkey,
+    mint: Pubkey,
+    team_wallet: Option<Pubkey>,
+    args: InitProjectArgs
+) -> Instruction {
+    init_project_with(signer, mint, team_wallet, args, LaunchOptions::default())
+}
+
+pub fn init_project_with(
+    signer: Pubkey,
+    mint: Pubkey,
+    team_wallet: Option<Pubkey>,
+    args: InitProjectArgs,
+    options: LaunchOptions
+) -> Instruction {
+    build(
+        accounts::InitProject {
+            project: pda::project(&mint).0,
+            config: pda::config().0,
+            vault: pda::vault().0,
+            mint,
+            vault_token_account: pda::vault_token_account(&mint),
+            team_token_account: team_wallet.map(|wallet| get_associated_token_address(&wallet, &mint)),
+            team_wallet,
+            fee_override: options.fee_override.then(|| pda::fee_override(&signer).0),
+            discount_token_account: options.discount_token_account,
+            fee_mint: options.fee_mint.map(|fee_mint| pda::fee_mint(&fee_mint).0),
+            fee_token_mint: options.fee_mint,
+            payer_token_account: options.fee_mint.map(|fee_mint| get_associated_token_address(&signer, &fee_mint)),
+            fee_vault: options.fee_mint.map(|fee_mint| pda::fee_vault(&fee_mint)),
+            referral: options.referrer.map(|referrer| pda::referral(&referrer).0),
+            referrer: options.referrer.filter(|_| options.fee_mint.is_none()),
+            referrer_token_account: options.referrer
+                .zip(options.fee_mint)
+                .map(|(referrer, fee_mint)| get_associated_token_address(&referrer, &fee_mint)),
+            referral_earnings: options.referrer
+                .zip(options.fee_mint)
+                .map(|(referrer, fee_mint)| pda::referral_earnings(&referrer, &fee_mint).0),
+            creator_permit: options.creator_permit.then(|| pda::creator_permit(&signer).0),
+            metadata: pda::metadata(&mint),
+            signer,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            metadata_program: mpl_token_metadata::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID
+        },
+        instruction::InitProject { args }
+    )
+}
+
+pub struct InitializeDaoArgs {
+    pub name: String,
+    pub supply: u64,
+    pub min_vote_to_govern: u64,
+    pub quorum: u8,
+    pub vote_duration: u32
+}
+
+/// Realm, governance and treasury addresses used by `initialize_dao`.
+pub struct DaoAddresses {
+    pub realm: Pubkey,
+    pub community_token_holding: Pubkey,
+    pub council_token_holding: Option<Pubkey>,
+    pub realm_config: Pubkey,
+    pub governance: Pubkey,
+    pub native_treasury: Pubkey,
+    pub dao_token_account: Pubkey
+}
+
+impl DaoAddresses {
+    pub fn derive(name: &str, mint: &Pubkey, council_mint: Option<&Pubkey>, governed_account: &Pubkey) -> Self {
+        let realm = pda::realm(name);
+        let governance = pda::governance(&realm, governed_account);
+        let native_treasury = pda::native_treasury(&governance);
+
+        Self {
+            realm,
+            community_token_holding: pda::governing_token_holding(&realm, mint),
+            council_token_holding: council_mint.map(|council| pda::governing_token_holding(&realm, council)),
+            realm_config: pda::realm_config(&realm),
+            governance,
+            native_treasury,
+            dao_token_account: get_associated_token_address(&native_treasury, mint)
+        }
+    }
+}
+
+/// `council_mint` must also sign the transaction when given, since the
+/// program creates it.
+pub fn initialize_dao(
+    signer: Pubkey,
+    mint: Pubkey,
+    council_mint: Option<Pubkey>,
+    governed_account: Pubkey,
+    args: InitializeDaoArgs
+) -> Instruction {
+    let dao = DaoAddresses::derive(&args.name, &mint, council_mint.as_ref(), &governed_account);
+
+    build(
+        accounts::InitializeDao {
+            signer,
+            config: pda::config().0,
+            project: pda::project(&mint).0,
+            mint,
+            council_mint,
+            realm_account: dao.realm,
+            community_token_holding: dao.community_token_holding,
+            council_token_holding: dao.council_token_holding,
+            realm_config: dao.realm_config,
+            governance: dao.governance,
+            governed_account,
+            native_treasury: dao.native_treasury,
+            dao_token_account: dao.dao_token_account,
+            realm_program: REALMS_ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            rent: sysvar::rent::ID
+        },
+        instruction::InitializeDao {
+            name: args.name,
+            supply: args.supply,
+            min_vote_to_govern: args.min_vote_to_govern,
+            is_council: council_mint.is_some(),
+            quorum: args.quorum,
+            vote_duration: args.vote_duration
+        }
+    )
+}
+
+/// Addresses of a Raydium CP-Swap pool pairing the project token with
+/// `quote_mint` under `amm_config`.
+pub struct CpSwapPool {
+    pub program: Pubkey,
+    pub amm_config: Pubkey,
+    pub authority: Pubkey,
+    pub pool_state: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub token_0_vault: Pubkey,
+    pub token_1_vault: Pubkey,
+    pub observation_state: Pubkey
+}
+
+impl CpSwapPool {
+    pub fn derive(program: Pubkey, amm_config: Pubkey, mint: &Pubkey, quote_mint: &Pubkey) -> Self {
+        let (token_0_mint, token_1_mint) = if mint < quote_mint { (*mint, *quote_mint) } else { (*quote_mint, *mint) };
+        let find = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &program).0;
+
+        let pool_state = find(&[b"pool", amm_config.as_ref(), token_0_mint.as_ref(), token_1_mint.as_ref()]);
+
+        Self {
+            program,
+            amm_config,
+            authority: find(&[b"vault_and_lp_mint_auth_seed"]),
+            pool_state,
+            token_0_mint,
+            token_1_mint,
+            lp_mint: find(&[b"pool_lp_mint", pool_state.as_ref()]),
+            token_0_vault: find(&[b"pool_vault", pool_state.as_ref(), token_0_mint.as_ref()]),
+            token_1_vault: find(&[b"pool_vault", pool_state.as_ref(), token_1_mint.as_ref()]),
+            observation_state: find(&[b"observation", pool_state.as_ref()])
+        }
+    }
+
+    /// Accounts of CP-Swap's `initialize` as `initialize_lp` expects them,
+    /// depositing from the creator's associated token accounts.
+    /// `create_pool_fee` is the AMM's pool creation fee receiver.
+    pub fn accounts(&self, creator: &Pubkey, create_pool_fee: Pubkey) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(self.amm_config, false),
+            AccountMeta::new_readonly(self.authority, false),
+            AccountMeta::new(self.pool_state, false),
+            AccountMeta::new_readonly(self.token_0_mint, false),
+            AccountMeta::new_readonly(self.token_1_mint, false),
+            AccountMeta::new(self.lp_mint, false),
+            AccountMeta::new(get_associated_token_address(creator, &self.token_0_mint), false),
+            AccountMeta::new(get_associated_token_address(creator, &self.token_1_mint), false),
+            AccountMeta::new(get_associated_token_address(creator, &self.lp_mint), false),
+            AccountMeta::new(self.token_0_vault, false),
+            AccountMeta::new(self.token_1_vault, false),
+            AccountMeta::new(create_pool_fee, false),
+            AccountMeta::new(self.observation_state, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new_readonly(anchor_spl::associated_token::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false)
+        ]
+    }
+}
+
+/// `amm_accounts` are the accounts the AMM's adapter expects, e.g.
+/// `CpSwapPool::accounts`.
+pub fn initialize_lp(
+    signer: Pubkey,
+    mint: Pubkey,
+    amm_program: Pubkey,
+    amm_accounts: Vec<AccountMeta>,
+    args: SeedLiquidityArgs
+) -> Instruction {
+    let mut ix = build(
+        accounts::InitializeLp {
+            signer,
+            config: pda::config().0,
+            project: pda::project(&mint).0,
+            vault: pda::vault().0,
+            vault_token_account: pda::vault_token_account(&mint),
+            creator_token_account: get_associated_token_address(&signer, &mint),
+            mint,
+            amm_program,
+            associated_token_program: anchor_spl::associated_token::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID
+        },
+        instruction::InitializeLp { args }
+    );
+
+    ix.accounts.extend(amm_accounts);
+    ix
+}
+
+pub fn airdrop_tokens(signer: Pubkey, mint: Pubkey, receiver: Pubkey, amount: u64) -> Instruction {
+    airdrop_tokens_with(signer, mint, receiver, amount, false)
+}
+
+/// `operator` signs in place of the creator, passing its role PDA.
+pub fn airdrop_tokens_as_operator(operator: Pubkey, mint: Pubkey, receiver: Pubkey, amount: u64) -> Instruction {
+    airdrop_tokens_with(operator, mint, receiver, amount, true)
+}
+
+fn airdrop_tokens_with(signer: Pubkey, mint: Pubkey, receiver: Pubkey, amount: u64, operator: bool) -> Instruction {
+    let project = pda::project(&mint).0;
+
+    build(
+        accounts::AirdropTokens {
+            signer,
+            config: pda::config().0,
+            project,
+            vault: pda::vault().0,
+            vault_token_account: pda::vault_token_account(&mint),
+            recipient_token_account: get_associated_token_address(&receiver, &mint),
+            receiver,
+            mint,
+            role: operator.then(|| pda::project_role(&project, &signer).0),
+            associated_token_program: anchor_spl::associated_token::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID
+        },
+        instruction::AirdropTokens { amount }
+    )
+}
+
+pub fn set_airdrop_deadline(signer: Pubkey, mint: Pubkey, airdrop_deadline: i64) -> Instruction {
+    build(
+        accounts::SetAirdropDeadline {
+            signer,
+            project: pda::project(&mint).0,
+            mint
+        },
+        instruction::SetAirdropDeadline { airdrop_deadline }
+    )
+}
+
+/// Only the destination required by the project's clawback policy is passed.
+pub fn close_airdrop(signer: Pubkey, project: &Project) -> Instruction {
+    let mint = project.mint;
+
+    let creator_token_account = (project.clawback_policy == ClawbackPolicy::Creator)
+        .then(|| get_associated_token_address(&project.creator, &mint));
+    let dao_token_account = (project.clawback_policy == ClawbackPolicy::Treasury)
+        .then(|| get_associated_token_address(&project.treasury, &mint));
+
+    build(
+        accounts::CloseAirdrop {
+            signer,
+            project: pda::project(&mint).0,
+            vault: pda::vault().0,
+            vault_token_account: pda::vault_token_account(&mint),
+            creator: project.creator,
+            creator_token_account,
+            dao_token_account,
+            mint,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID
+        },
+        instruction::CloseAirdrop {}
+    )
+}
+
+pub fn burn_vault_tokens(signer: Pubkey, mint: Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::BurnVaultTokens {
+            signer,
+            project: pda::project(&mint).0,
+            vault: pda::vault().0,
+            vault_token_account: pda::vault_token_account(&mint),
+            mint,
+            token_program: anchor_spl::token::ID
+        },
+        instruction::BurnVaultTokens { amount }
+    )
+}
+
+pub fn sweep_to_treasury(signer: Pubkey, mint: Pubkey, native_treasury: Pubkey) -> Instruction {
+    build(
+        accounts::SweepToTreasury {
+            signer,
+            project: pda::project(&mint).0,
+            vault: pda::vault().0,
+            vault_token_account: pda::vault_token_account(&mint),
+            native_treasury,
+            dao_token_account: get_associated_token_address(&native_treasury, &mint),
+            mint,
+            token_program: anchor_spl::token::ID
+        },
+        instruction::SweepToTreasury {}
+    )
+}
+
+pub fn create_sale(signer: Pubkey, mint: Pubkey, args: SaleArgs) -> Instruction {
+    let project = pda::project(&mint).0;
+
+    build(
+        accounts::CreateSale {
+            signer,
+            config: pda::config().0,
+            project,
+            sale: pda::sale(&project).0,
+            vault: pda::vault().0,
+            vault_token_account: pda::vault_token_account(&mint),
+            mint,
+            system_program: system_program::ID
+        },
+        instruction::CreateSale { args }
+    )
+}
+
+/// `proof` comes from `AllowlistTree::proof`, empty for an open sale.
+pub fn contribute(signer: Pubkey, mint: Pubkey, amount: u64, proof: Vec<[u8; 32]>) -> Instruction {
+    let project = pda::project(&mint).0;
+    let sale = pda::sale(&project).0;
+
+    build(
+        accounts::Contribute {
+            signer,
+            config: pda::config().0,
+            project,
+            sale,
+            contribution: pda::contribution(&sale, &signer).0,
+            system_program: system_program::ID
+        },
+        instruction::Contribute { amount, proof }
+    )
+}
+
+pub fn claim_sale_tokens(signer: Pubkey, mint: Pubkey) -> Instruction {
+    let project = pda::project(&mint).0;
+    let sale = pda::sale(&project).0;
+
+    build(
+        accounts::ClaimSaleTokens {
+            signer,
+            project,
+            sale,
+            contribution: pda::contribution(&sale, &signer).0,
+            vault: pda::vault().0,
+            vault_token_account: pda::vault_token_account(&mint),
+            contributor_token_account: get_associated_token_address(&signer, &mint),
+            mint,
+            associated_token_program: anchor_spl::associated_token::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID
+        },
+        instruction::ClaimSaleTokens {}
+    )
+}
+
+pub fn refund_contribution(signer: Pubkey, mint: Pubkey) -> Instruction {
+    let sale = pda::sale(&pda::project(&mint).0).0;
+
+    build(
+        accounts::RefundContribution {
+            signer,
+            sale,
+            contribution: pda::contribution(&sale, &signer).0
+        },
+        instruction::RefundContribution {}
+    )
+}
+
+pub fn withdraw_sale_proceeds(signer: Pubkey, mint: Pubkey) -> Instruction {
+    let project = pda::project(&mint).0;
+
+    build(
+        accounts::WithdrawSaleProceeds {
+            signer,
+            project,
+            sale: pda::sale(&project).0
+        },
+        instruction::WithdrawSaleProceeds {}
+    )
+}
+
+pub fn close_sale(signer: Pubkey, mint: Pubkey) -> Instruction {
+    let project = pda::project(&mint).0;
+
+    build(
+        accounts::CloseSale {
+            signer,
+            project,
+            sale: pda::sale(&project).0
+        },
+        instruction::CloseSale {}
+    )
+}
+
+pub fn create_auction(signer: Pubkey, mint: Pubkey, args: AuctionArgs) -> Instruction {
+    let project = pda::project(&mint).0;
+
+    build(
+        accounts::CreateAuction {
+            signer,
+            config: pda::config().0,
+            project,
+            auction: pda::auction(&project).0,
+            vault: pda::vault().0,
+            vault_token_account: pda::vault_token_account(&mint),
+            mint,
+            system_program: system_program::ID
+        },
+        instruction::CreateAuction { args }
+    )
+}
+
+pub fn place_bid(signer: Pubkey, mint: Pubkey, quantity: u64) -> Instruction {
+    let project = pda::project(&mint).0;
+    let auction = pda::auction(&project).0;
+
+    build(
+        accounts::PlaceBid {
+            signer,
+            config: pda::config().0,
+            project,
+            auction,
+            bid: pda::bid(&auction, &signer).0,
+            system_program: system_program::ID
+        },
+        instruction::PlaceBid { quantity }
+    )
+}
+
+pub fn claim_auction_tokens(signer: Pubkey, mint: Pubkey) -> Instruction {
+    let project = pda::project(&mint).0;
+    let auction = pda::auction(&project).0;
+
+    build(
+        accounts::ClaimAuctionTokens {
+            signer,
+            project,
+            auction,
+            bid: pda::bid(&auction, &signer).0,
+            vault: pda::vault().0,
+            vault_token_account: pda::vault_token_account(&mint),
+            bidder_token_account: get_associated_token_address(&signer, &mint),
+            mint,
+            associated_token_program: anchor_spl::associated_token::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID
+        },
+        instruction::ClaimAuctionTokens {}
+    )
+}
+
+/// `destination` must be the project's DAO treasury once the DAO is live,
+/// its creator before that.
+pub fn withdraw_auction_proceeds(signer: Pubkey, mint: Pubkey, destination: Pubkey) -> Instruction {
+    let project = pda::project(&mint).0;
+
+    build(
+        accounts::WithdrawAuctionProceeds {
+            signer,
+            project,
+            auction: pda::auction(&project).0,
+            destination
+        },
+        instruction::WithdrawAuctionProceeds {}
+    )
+}
+
+pub fn close_auction(signer: Pubkey, mint: Pubkey) -> Instruction {
+    let project = pda::project(&mint).0;
+
+    build(
+        accounts::CloseAuction {
+            signer,
+            project,
+            auction: pda::auction(&project).0
+        },
+        instruction::CloseAuction {}
+    )
+}
+
+/// Locks `amount` LP tokens from the signer's `lp_mint` account until
+/// `unlock_at`.
+pub fn lock_lp(signer: Pubkey, mint: Pubkey, lp_mint: Pubkey, amount: u64, unlock_at: i64) -> Instruction {
+    let project = pda::project(&mint).0;
+    let lp_lock = pda::lp_lock(&project).0;
+
+    build(
+        accounts::LockLp {
+            signer,
+            project,
+            lp_lock,
+            lp_mint,
+            source: get_associated_token_address(&signer, &lp_mint),
+            escrow: get_associated_token_address(&lp_lock, &lp_mint),
+            associated_token_program: anchor_spl::associated_token::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID
+        },
+        instruction::LockLp { amount, unlock_at }
+    )
+}
+
+pub fn make_lp_lock_permanent(signer: Pubkey, mint: Pubkey) -> Instruction {
+    let project = pda::project(&mint).0;
+
+    build(
+        accounts::UpdateLpLock {
+            signer,
+            project,
+            lp_lock: pda::lp_lock(&project).0
+        },
+        instruction::MakeLpLockPermanent {}
+    )
+}
+
+pub fn burn_locked_lp(signer: Pubkey, mint: Pubkey, lp_mint: Pubkey) -> Instruction {
+    let project = pda::project(&mint).0;
+    let lp_lock = pda::lp_lock(&project).0;
+
+    build(
+        accounts::BurnLockedLp {
+            signer,
+            project,
+            lp_lock,
+            lp_mint,
+            escrow: get_associated_token_address(&lp_lock, &lp_mint),
+            token_program: anchor_spl::token::ID
+        },
+        instruction::BurnLockedLp {}
+    )
+}
+
+/// Returns the locked LP tokens to `destination`, a token account for
+/// `lp_mint`.
+pub fn unlock_lp(signer: Pubkey, mint: Pubkey, lp_mint: Pubkey, destination: Pubkey) -> Instruction {
+    let project = pda::project(&mint).0;
+    let lp_lock = pda::lp_lock(&project).0;
+
+    build(
+        accounts::UnlockLp {
+            signer,
+            project,
+            lp_lock,
+            lp_mint,
+            escrow: get_associated_token_address(&lp_lock, &lp_mint),
+            destination,
+            token_program: anchor_spl::token::ID
+        },
+        instruction::UnlockLp {}
+    )
+}
+
+pub fn burn_authority(signer: Pubkey, mint: Pubkey) -> Instruction {
+    build(
+        accounts::BurnAuthority {
+            signer,
+            project: pda::project(&mint).0,
+            mint,
+            token_program: anchor_spl::token::ID
+        },
+        instruction::BurnAuthority {}
+    )
+}
+
+/// Marks `signer` verified in the token metadata of `mint`.
+pub fn verify_creator(signer: Pubkey, mint: Pubkey) -> Instruction {
+    build(
+        accounts::VerifyCreator {
+            signer,
+            project: pda::project(&mint).0,
+            mint,
+            metadata: pda::metadata(&mint),
+            metadata_program: mpl_token_metadata::ID
+        },
+        instruction::VerifyCreator {}
+    )
+}
+
+/// Pass the default key as `new_creator` to cancel a pending handover.
+pub fn transfer_project_ownership(signer: Pubkey, mint: Pubkey, new_creator: Pubkey) -> Instruction {
+    build(
+        accounts::TransferProjectOwnership {
+            signer,
+            project: pda::project(&mint).0,
+            mint,
+            metadata: pda::metadata(&mint),
+            metadata_program: mpl_token_metadata::ID
+        },
+        instruction::TransferProjectOwnership { new_creator }
+    )
+}
+
+pub fn accept_project_ownership(signer: Pubkey, mint: Pubkey) -> Instruction {
+    build(
+        accounts::AcceptProjectOwnership {
+            signer,
+            project: pda::project(&mint).0,
+            metadata: pda::metadata(&mint),
+            metadata_program: mpl_token_metadata::ID
+        },
+        instruction::AcceptProjectOwnership {}
+    )
+}
+
+/// `roles` is a mask of `ROLE_*` bits.
+pub fn set_project_role(signer: Pubkey, mint: Pubkey, operator: Pubkey, roles: u8) -> Instruction {
+    let project = pda::project(&mint).0;
+
+    build(
+        accounts::SetProjectRole {
+            signer,
+            project,
+            role: pda::project_role(&project, &operator).0,
+            system_program: system_program::ID
+        },
+        instruction::SetProjectRole { operator, roles }
+    )
+}
+
+pub fn remove_project_role(signer: Pubkey, mint: Pubkey, operator: Pubkey) -> Instruction {
+    let project = pda::project(&mint).0;
+
+    build(
+        accounts::RemoveProjectRole {
+            signer,
+            project,
+            role: pda::project_role(&project, &operator).0
+        },
+        instruction::RemoveProjectRole { _operator: operator }
+    )
+}
+
+pub fn set_project_profile(signer: Pubkey, mint: Pubkey, data: ProfileData) -> Instruction {
+    let project = pda::project(&mint).0;
+
+    build(
+        accounts::SetProjectProfile {
+            signer,
+            project,
+            profile: pda::project_profile(&project).0,
+            system_program: system_program::ID
+        },
+        instruction::SetProjectProfile { data }
+    )
+}
+
+pub fn close_project_profile(signer: Pubkey, mint: Pubkey) -> Instruction {
+    let project = pda::project(&mint).0;
+
+    build(
+        accounts::CloseProjectProfile {
+            signer,
+            project,
+            profile: pda::project_profile(&project).0
+        },
+        instruction::CloseProjectProfile {}
+    )
+}
+
+pub fn close_project(signer: Pubkey, mint: Pubkey) -> Instruction {
+    let project = pda::project(&mint).0;
+
+    build(
+        accounts::CloseProject {
+            signer,
+            project,
+            vault: pda::vault().0,
+            vault_token_account: pda::vault_token_account(&mint),
+            mint,
+            profile: pda::project_profile(&project).0,
+            sale: pda::sale(&project).0,
+            auction: pda::auction(&project).0,
+            token_program: anchor_spl::token::ID
+        },
+        instruction::CloseProject {}
+    )
+}
+
+/// Adds the project of `mint` to the registry under `id`, the config's
+/// current `project_count`; fails if another project takes that id first.
+/// `page` is the first page of the creator's list that isn't full.
+pub fn register_project(signer: Pubkey, mint: Pubkey, creator: Pubkey, id: u64, page: u16) -> Instruction {
+    build(
+        accounts::RegisterProject {
+            signer,
+            config: pda::config().0,
+            project: pda::project(&mint).0,
+            project_index: pda::project_index(id).0,
+            creator_registry: pda::creator_registry(&creator, page).0,
+            previous_page: page.checked_sub(1).map(|page| pda::creator_registry(&creator, page).0),
+            system_program: system_program::ID
+        },
+        instruction::RegisterProject { page }
+    )
+}
+
+pub fn migrate_project(signer: Pubkey, mint: Pubkey) -> Instruction {
+    build(
+        accounts::MigrateProject {
+            signer,
+            project: pda::project(&mint).0,
+            mint,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID
+        },
+        instruction::MigrateProject {}
+    )
+}
+
+pub fn migrate_config(signer: Pubkey) -> Instruction {
+    build(
+        accounts::MigrateConfig {
+            signer,
+            config: pda::config().0,
+            proposal: None,
+            system_program: system_program::ID
+        },
+        instruction::MigrateConfig {}
+    )
+}
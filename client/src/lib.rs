@@ -0,0 +1,20 @@
+//! Client helpers for the Tatami launch program.
+//!
+//! Builds instructions with the right account lists, derives the program's
+//! PDAs and decodes its accounts and errors, so off-chain services don't have
+//! to mirror the program by hand.
+
+pub mod accounts;
+pub mod allowlist;
+pub mod errors;
+pub mod instructions;
+pub mod pda;
+
+pub use tatami_v2::{
+    allowlist_leaf, permit_message, AdminAction, AmmKind, ApprovedAmm, Auction, AuctionArgs, Bid, ClawbackPolicy,
+    Config, Contribution, CreatorPermit, CreatorRegistry, CreatorShare, Errors, FeeMint, FeeOverride, FeeSchedule,
+    LpLock, LpLockStatus, ProfileData, Project, ProjectCategory, ProjectIndex, ProjectProfile, ProjectRole,
+    ProjectState, Proposal, Referral, ReferralEarnings, Sale, SaleArgs, SeedLiquidityArgs, FEATURE_DAO, FEATURE_LP,
+    FEATURE_VESTING, ID as PROGRAM_ID, MAX_CREATOR_PROJECTS, PAUSE_AIRDROP, PAUSE_INIT_DAO, PAUSE_INIT_LP,
+    PAUSE_INIT_PROJECT, PAUSE_SALE, ROLE_AIRDROP
+};
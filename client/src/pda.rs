@@ -0,0 +1,117 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::{associated_token::get_associated_token_address, metadata::mpl_token_metadata};
+use tatami_v2::REALMS_ID;
+
+pub fn config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-config"], &tatami_v2::ID)
+}
+
+pub fn vault() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-vault"], &tatami_v2::ID)
+}
+
+pub fn project(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-project", mint.as_ref()], &tatami_v2::ID)
+}
+
+pub fn project_index(id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-index", &id.to_le_bytes()], &tatami_v2::ID)
+}
+
+pub fn creator_registry(creator: &Pubkey, page: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-creator", creator.as_ref(), &page.to_le_bytes()], &tatami_v2::ID)
+}
+
+pub fn project_role(project: &Pubkey, operator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-role", project.as_ref(), operator.as_ref()], &tatami_v2::ID)
+}
+
+pub fn project_profile(project: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-profile", project.as_ref()], &tatami_v2::ID)
+}
+
+pub fn sale(project: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-sale", project.as_ref()], &tatami_v2::ID)
+}
+
+pub fn contribution(sale: &Pubkey, contributor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-contribution", sale.as_ref(), contributor.as_ref()], &tatami_v2::ID)
+}
+
+pub fn auction(project: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-auction", project.as_ref()], &tatami_v2::ID)
+}
+
+pub fn bid(auction: &Pubkey, bidder: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-bid", auction.as_ref(), bidder.as_ref()], &tatami_v2::ID)
+}
+
+pub fn lp_lock(project: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-lp-lock", project.as_ref()], &tatami_v2::ID)
+}
+
+pub fn fee_override(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-fee-override", creator.as_ref()], &tatami_v2::ID)
+}
+
+pub fn proposal(id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-proposal", &id.to_le_bytes()], &tatami_v2::ID)
+}
+
+pub fn creator_permit(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-permit", creator.as_ref()], &tatami_v2::ID)
+}
+
+pub fn referral(referrer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-referral", referrer.as_ref()], &tatami_v2::ID)
+}
+
+/// Running total of `referrer`'s fee shares paid in `mint`.
+pub fn referral_earnings(referrer: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"tatami-referral-earnings", referrer.as_ref(), mint.as_ref()],
+        &tatami_v2::ID
+    )
+}
+
+pub fn fee_mint(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tatami-fee-mint", mint.as_ref()], &tatami_v2::ID)
+}
+
+/// Config-owned token account collecting fees paid in `mint`.
+pub fn fee_vault(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&config().0, mint)
+}
+
+/// Token account holding the undistributed supply of `mint`.
+pub fn vault_token_account(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&vault().0, mint)
+}
+
+pub fn metadata(mint: &Pubkey) -> Pubkey {
+    let program = mpl_token_metadata::ID;
+    Pubkey::find_program_address(&[b"metadata", program.as_ref(), mint.as_ref()], &program).0
+}
+
+pub fn realm(name: &str) -> Pubkey {
+    Pubkey::find_program_address(&[b"governance", name.as_bytes()], &REALMS_ID).0
+}
+
+pub fn governing_token_holding(realm: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"governance", realm.as_ref(), mint.as_ref()], &REALMS_ID).0
+}
+
+pub fn realm_config(realm: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"realm-config", realm.as_ref()], &REALMS_ID).0
+}
+
+pub fn governance(realm: &Pubkey, governed_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"account-governance", realm.as_ref(), governed_account.as_ref()],
+        &REALMS_ID
+    ).0
+}
+
+pub fn native_treasury(governance: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"native-treasury", governance.as_ref()], &REALMS_ID).0
+}
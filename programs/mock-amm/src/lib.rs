@@ -0,0 +1,267 @@
+//! Stand-in for Raydium CP-Swap in tests. `initialize` takes the same
+//! accounts, seeds and arguments as the real program, so Tatami's adapter
+//! runs unchanged against it, but there is no swapping, fee or oracle logic.
+
+use anchor_lang::prelude::*;
+use anchor_spl::{token::{Mint, Token, TokenAccount, Transfer, transfer, MintTo, mint_to},
+    associated_token::AssociatedToken};
+
+declare_id!("9JX9UtqZQ3RESgtYKVUnQKxVd5LPneMFQyH8w8AR2NL3");
+
+pub const AMM_CONFIG_SEED: &[u8] = b"amm_config";
+pub const AUTH_SEED: &[u8] = b"vault_and_lp_mint_auth_seed";
+pub const POOL_SEED: &[u8] = b"pool";
+pub const POOL_LP_MINT_SEED: &[u8] = b"pool_lp_mint";
+pub const POOL_VAULT_SEED: &[u8] = b"pool_vault";
+pub const OBSERVATION_SEED: &[u8] = b"observation";
+
+/// LP tokens kept back from the first deposit, as CP-Swap does.
+pub const LOCKED_LIQUIDITY: u64 = 100;
+
+#[program]
+pub mod mock_amm {
+    use super::*;
+
+    pub fn create_amm_config(ctx: Context<CreateAmmConfig>, index: u16) -> Result<()> {
+        let amm_config = &mut ctx.accounts.amm_config;
+
+        amm_config.index = index;
+        amm_config.bump = ctx.bumps.amm_config;
+        Ok(())
+    }
+
+    /// Creates the pool and deposits both sides, minting the creator
+    /// sqrt(amount_0 * amount_1) LP tokens less `LOCKED_LIQUIDITY`.
+    pub fn initialize(ctx: Context<Initialize>, init_amount_0: u64, init_amount_1: u64, open_time: u64) -> Result<()> {
+        let liquidity = sqrt(init_amount_0 as u128 * init_amount_1 as u128) as u64;
+        require_gt!(liquidity, LOCKED_LIQUIDITY, MockAmmError::InsufficientLiquidity);
+
+        transfer(ctx.accounts.deposit_ctx(0), init_amount_0)?;
+        transfer(ctx.accounts.deposit_ctx(1), init_amount_1)?;
+
+        let bump = ctx.bumps.authority;
+        mint_to(ctx.accounts.mint_lp_ctx().with_signer(&[&[AUTH_SEED, &[bump]]]), liquidity - LOCKED_LIQUIDITY)?;
+
+        let pool_state = &mut ctx.accounts.pool_state;
+
+        pool_state.amm_config = ctx.accounts.amm_config.key();
+        pool_state.pool_creator = ctx.accounts.creator.key();
+        pool_state.token_0_vault = ctx.accounts.token_0_vault.key();
+        pool_state.token_1_vault = ctx.accounts.token_1_vault.key();
+        pool_state.lp_mint = ctx.accounts.lp_mint.key();
+        pool_state.token_0_mint = ctx.accounts.token_0_mint.key();
+        pool_state.token_1_mint = ctx.accounts.token_1_mint.key();
+        pool_state.lp_supply = liquidity;
+        pool_state.open_time = open_time;
+        pool_state.bump = ctx.bumps.pool_state;
+
+        ctx.accounts.observation_state.pool_id = pool_state.key();
+        Ok(())
+    }
+}
+
+/// Integer square root, rounded down.
+fn sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+#[derive(Accounts)]
+#[instruction(index: u16)]
+pub struct CreateAmmConfig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + AmmConfig::INIT_SPACE,
+        seeds = [
+            AMM_CONFIG_SEED,
+            index.to_be_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub amm_config: Account<'info, AmmConfig>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+    /// CHECK: Signs for the vaults and the LP mint
+    #[account(
+        seeds = [AUTH_SEED],
+        bump
+    )]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PoolState::INIT_SPACE,
+        seeds = [
+            POOL_SEED,
+            amm_config.key().as_ref(),
+            token_0_mint.key().as_ref(),
+            token_1_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+    #[account(constraint = token_0_mint.key() < token_1_mint.key() @ MockAmmError::InvalidMintOrder)]
+    pub token_0_mint: Box<Account<'info, Mint>>,
+    pub token_1_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            POOL_LP_MINT_SEED,
+            pool_state.key().as_ref()
+        ],
+        bump,
+        mint::decimals = 9,
+        mint::authority = authority
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = token_0_mint,
+        token::authority = creator
+    )]
+    pub creator_token_0: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_1_mint,
+        token::authority = creator
+    )]
+    pub creator_token_1: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = lp_mint,
+        associated_token::authority = creator
+    )]
+    pub creator_lp_token: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            POOL_VAULT_SEED,
+            pool_state.key().as_ref(),
+            token_0_mint.key().as_ref()
+        ],
+        bump,
+        token::mint = token_0_mint,
+        token::authority = authority
+    )]
+    pub token_0_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            POOL_VAULT_SEED,
+            pool_state.key().as_ref(),
+            token_1_mint.key().as_ref()
+        ],
+        bump,
+        token::mint = token_1_mint,
+        token::authority = authority
+    )]
+    pub token_1_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Receives CP-Swap's pool creation fee, the mock charges none
+    #[account(mut)]
+    pub create_pool_fee: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + ObservationState::INIT_SPACE,
+        seeds = [
+            OBSERVATION_SEED,
+            pool_state.key().as_ref()
+        ],
+        bump
+    )]
+    pub observation_state: Box<Account<'info, ObservationState>>,
+    pub token_program: Program<'info, Token>,
+    pub token_0_program: Program<'info, Token>,
+    pub token_1_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>
+}
+
+impl<'info> Initialize<'info> {
+    pub fn deposit_ctx(&self, side: usize) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let (from, to) = match side {
+            0 => (&self.creator_token_0, &self.token_0_vault),
+            _ => (&self.creator_token_1, &self.token_1_vault)
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: self.creator.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    pub fn mint_lp_ctx(&self) -> CpiContext<'_, '_, '_, 'info, MintTo<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = MintTo {
+            mint: self.lp_mint.to_account_info(),
+            to: self.creator_lp_token.to_account_info(),
+            authority: self.authority.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AmmConfig {
+    pub index: u16,
+    pub bump: u8
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PoolState {
+    pub amm_config: Pubkey,
+    pub pool_creator: Pubkey,
+    pub token_0_vault: Pubkey,
+    pub token_1_vault: Pubkey,
+    pub lp_mint: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    /// LP supply including the locked liquidity.
+    pub lp_supply: u64,
+    pub open_time: u64,
+    pub bump: u8
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ObservationState {
+    pub pool_id: Pubkey
+}
+
+#[error_code]
+pub enum MockAmmError {
+    #[msg("token_0_mint must sort before token_1_mint")]
+    InvalidMintOrder,
+    #[msg("initial deposit is too small")]
+    InsufficientLiquidity
+}
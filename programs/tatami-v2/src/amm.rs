@@ -0,0 +1,206 @@
+//! Seeding liquidity on external AMMs. Each AMM family gets an adapter that
+//! checks the accounts it's handed against the layout that AMM expects, then
+//! creates the pool by CPI. Only programs the admin approved in `Config` are
+//! ever invoked.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke, instruction::Instruction, sysvar};
+use anchor_spl::associated_token::get_associated_token_address;
+
+use crate::Errors;
+
+/// How an approved AMM program is driven.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmmKind {
+    /// Raydium CP-Swap, or a fork with the same `initialize` layout.
+    CpSwap
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApprovedAmm {
+    pub program: Pubkey,
+    pub kind: AmmKind
+}
+
+impl Default for ApprovedAmm {
+    fn default() -> Self {
+        Self { program: Pubkey::default(), kind: AmmKind::CpSwap }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SeedLiquidityArgs {
+    /// Project tokens moved out of the vault into the pool.
+    pub token_amount: u64,
+    /// Quote tokens the creator deposits against them.
+    pub quote_amount: u64,
+    /// Unix timestamp trading opens at, anything in the past opens it now.
+    pub open_time: u64
+}
+
+/// Creates a pool pairing the project token with a quote token and makes its
+/// first deposit. The creator signs the deposit, and the project tokens are
+/// already in `creator_token_account` when `create_pool` runs.
+pub trait AmmAdapter<'info>: Sized {
+    /// Takes the AMM's accounts, in the AMM's own order, off the front of
+    /// `accounts`, failing with `InvalidAmmAccounts` unless they match the
+    /// pool `creator` is about to open for `mint`.
+    fn load(
+        program: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        creator: &Pubkey,
+        creator_token_account: &Pubkey,
+        mint: &Pubkey
+    ) -> Result<Self>;
+
+    fn create_pool(&self, args: &SeedLiquidityArgs) -> Result<()>;
+
+    fn pool(&self) -> Pubkey;
+
+    fn lp_mint(&self) -> Pubkey;
+}
+
+/// Loads `A` and seeds the pool, returning the pool and its LP mint.
+pub fn seed_liquidity<'info, A: AmmAdapter<'info>>(
+    program: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    creator: &Pubkey,
+    creator_token_account: &Pubkey,
+    mint: &Pubkey,
+    args: &SeedLiquidityArgs
+) -> Result<(Pubkey, Pubkey)> {
+    let adapter = A::load(program, accounts, creator, creator_token_account, mint)?;
+
+    adapter.create_pool(args)?;
+    Ok((adapter.pool(), adapter.lp_mint()))
+}
+
+/// Raydium CP-Swap's `initialize`. Pools are keyed by config and sorted mint
+/// pair, so every other account is derived from those and checked here.
+pub struct CpSwap<'info> {
+    program: AccountInfo<'info>,
+    accounts: Vec<AccountInfo<'info>>,
+    /// Whether the project token is `token_0` of the pair.
+    mint_is_token_0: bool
+}
+
+impl<'info> CpSwap<'info> {
+    pub const ACCOUNTS: usize = 20;
+    /// Anchor discriminator of `initialize`.
+    pub const INITIALIZE: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+
+    pub const CREATOR: usize = 0;
+    pub const AMM_CONFIG: usize = 1;
+    pub const AUTHORITY: usize = 2;
+    pub const POOL_STATE: usize = 3;
+    pub const TOKEN_0_MINT: usize = 4;
+    pub const TOKEN_1_MINT: usize = 5;
+    pub const LP_MINT: usize = 6;
+    pub const CREATOR_TOKEN_0: usize = 7;
+    pub const CREATOR_TOKEN_1: usize = 8;
+    pub const CREATOR_LP_TOKEN: usize = 9;
+    pub const TOKEN_0_VAULT: usize = 10;
+    pub const TOKEN_1_VAULT: usize = 11;
+    pub const CREATE_POOL_FEE: usize = 12;
+    pub const OBSERVATION_STATE: usize = 13;
+    pub const TOKEN_PROGRAM: usize = 14;
+    pub const TOKEN_0_PROGRAM: usize = 15;
+    pub const TOKEN_1_PROGRAM: usize = 16;
+    pub const ASSOCIATED_TOKEN_PROGRAM: usize = 17;
+    pub const SYSTEM_PROGRAM: usize = 18;
+    pub const RENT: usize = 19;
+
+    fn key(&self, index: usize) -> Pubkey {
+        self.accounts[index].key()
+    }
+}
+
+fn derive(seeds: &[&[u8]], program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(seeds, program).0
+}
+
+impl<'info> AmmAdapter<'info> for CpSwap<'info> {
+    fn load(
+        program: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        creator: &Pubkey,
+        creator_token_account: &Pubkey,
+        mint: &Pubkey
+    ) -> Result<Self> {
+        require_gte!(accounts.len(), Self::ACCOUNTS, Errors::InvalidAmmAccounts);
+
+        let adapter = Self {
+            program: program.clone(),
+            accounts: accounts[..Self::ACCOUNTS].to_vec(),
+            mint_is_token_0: accounts[Self::TOKEN_0_MINT].key == mint
+        };
+        let key = |index| adapter.key(index);
+        let amm = program.key;
+
+        let (token_0, token_1) = (key(Self::TOKEN_0_MINT), key(Self::TOKEN_1_MINT));
+        require!(token_0 == *mint || token_1 == *mint, Errors::InvalidAmmAccounts);
+        require!(token_0 < token_1, Errors::InvalidAmmAccounts);
+
+        let amm_config = &adapter.accounts[Self::AMM_CONFIG];
+        require_keys_eq!(*amm_config.owner, *amm, Errors::InvalidAmmAccounts);
+
+        let pool = derive(&[b"pool", amm_config.key.as_ref(), token_0.as_ref(), token_1.as_ref()], amm);
+        let lp_mint = derive(&[b"pool_lp_mint", pool.as_ref()], amm);
+        let creator_side = if adapter.mint_is_token_0 { Self::CREATOR_TOKEN_0 } else { Self::CREATOR_TOKEN_1 };
+
+        let expected = [
+            (Self::CREATOR, *creator),
+            (Self::AUTHORITY, derive(&[b"vault_and_lp_mint_auth_seed"], amm)),
+            (Self::POOL_STATE, pool),
+            (Self::LP_MINT, lp_mint),
+            (creator_side, *creator_token_account),
+            (Self::CREATOR_LP_TOKEN, get_associated_token_address(creator, &lp_mint)),
+            (Self::TOKEN_0_VAULT, derive(&[b"pool_vault", pool.as_ref(), token_0.as_ref()], amm)),
+            (Self::TOKEN_1_VAULT, derive(&[b"pool_vault", pool.as_ref(), token_1.as_ref()], amm)),
+            (Self::OBSERVATION_STATE, derive(&[b"observation", pool.as_ref()], amm)),
+            (Self::TOKEN_PROGRAM, anchor_spl::token::ID),
+            (Self::TOKEN_0_PROGRAM, anchor_spl::token::ID),
+            (Self::TOKEN_1_PROGRAM, anchor_spl::token::ID),
+            (Self::ASSOCIATED_TOKEN_PROGRAM, anchor_spl::associated_token::ID),
+            (Self::SYSTEM_PROGRAM, System::id()),
+            (Self::RENT, sysvar::rent::ID)
+        ];
+
+        for (index, address) in expected {
+            require_keys_eq!(key(index), address, Errors::InvalidAmmAccounts);
+        }
+
+        Ok(adapter)
+    }
+
+    fn create_pool(&self, args: &SeedLiquidityArgs) -> Result<()> {
+        let (init_amount_0, init_amount_1) = if self.mint_is_token_0 {
+            (args.token_amount, args.quote_amount)
+        } else {
+            (args.quote_amount, args.token_amount)
+        };
+
+        let mut data = Self::INITIALIZE.to_vec();
+        (init_amount_0, init_amount_1, args.open_time).serialize(&mut data)?;
+
+        let ix = Instruction {
+            program_id: self.program.key(),
+            accounts: self.accounts.to_account_metas(None),
+            data
+        };
+
+        let mut infos = self.accounts.clone();
+        infos.push(self.program.clone());
+
+        invoke(&ix, &infos)?;
+        Ok(())
+    }
+
+    fn pool(&self) -> Pubkey {
+        self.key(Self::POOL_STATE)
+    }
+
+    fn lp_mint(&self) -> Pubkey {
+        self.key(Self::LP_MINT)
+    }
+}
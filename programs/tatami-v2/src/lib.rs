@@ -1,58 +1,544 @@
-use anchor_lang::{prelude::*, system_program::{Transfer, transfer}};
-use solana_program::{pubkey, pubkey::Pubkey};
-use anchor_lang::solana_program::{program::invoke, instruction::Instruction};
+use anchor_lang::{prelude::*, system_program::{Transfer, transfer}, Discriminator};
+use solana_program::{pubkey, pubkey::Pubkey, native_token::LAMPORTS_PER_SOL, program_option::COption};
+use anchor_lang::solana_program::{
+    program::invoke, instruction::Instruction, ed25519_program, keccak,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked}
+};
 use anchor_spl::{token::{Mint, Token, SetAuthority, 
     set_authority, spl_token::instruction::AuthorityType,
     mint_to, MintTo, TokenAccount,
-    TransferChecked, transfer_checked
-}, associated_token::AssociatedToken};
+    TransferChecked, transfer_checked,
+    Burn, burn,
+    CloseAccount, close_account
+}, associated_token::{AssociatedToken, get_associated_token_address}};
 use anchor_spl::metadata::{
-    Metadata, CreateMetadataAccountsV3, create_metadata_accounts_v3
+    Metadata, MetadataAccount, CreateMetadataAccountsV3, create_metadata_accounts_v3,
+    UpdateMetadataAccountsV2, update_metadata_accounts_v2,
+    SignMetadata, sign_metadata
 };
 
+pub mod amm;
+
+pub use amm::{AmmKind, ApprovedAmm, SeedLiquidityArgs};
+use amm::{seed_liquidity, CpSwap};
+
 declare_id!("HrKLeJB6yoSWkFzVSfsg8Yi3Zs4PKZ7qqjkMz978qqZv");
 
 #[constant]
 pub const REALMS_ID: Pubkey = pubkey!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
 
+#[constant]
+pub const PROJECT_VERSION: u8 = 2;
+
+#[constant]
+pub const CONFIG_VERSION: u8 = 1;
+
+/// Optional launch features, paid for in `init_project`.
+#[constant]
+pub const FEATURE_DAO: u8 = 1 << 0;
+
+#[constant]
+pub const FEATURE_LP: u8 = 1 << 1;
+
+#[constant]
+pub const FEATURE_VESTING: u8 = 1 << 2;
+
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// Roles a project creator can delegate to operators.
+#[constant]
+pub const ROLE_AIRDROP: u8 = 1 << 0;
+
+/// Most creators token metadata can list.
+pub const MAX_CREATORS: usize = 5;
+
+/// Most decimals a launched mint can have, keeping auction prices, which are
+/// per whole token, within u64 lamports.
+pub const MAX_DECIMALS: u8 = 9;
+
+/// Size of the optional admin set on `Config`.
+pub const MAX_ADMINS: usize = 5;
+
+/// Most AMM programs `Config` can approve for `initialize_lp`.
+pub const MAX_AMM_PROGRAMS: usize = 4;
+
+/// Length limits of a `ProjectProfile`, in bytes for strings.
+pub const MAX_PROFILE_LINK_LEN: usize = 100;
+pub const MAX_PROFILE_SOCIALS: usize = 4;
+pub const MAX_PROFILE_TAG_LEN: usize = 16;
+pub const MAX_PROFILE_TAGS: usize = 5;
+
+/// Most projects one page of a creator's `CreatorRegistry` lists.
+pub const MAX_CREATOR_PROJECTS: usize = 64;
+
+/// Instructions the admin can pause one by one, on top of the global flag.
+#[constant]
+pub const PAUSE_INIT_PROJECT: u8 = 1 << 0;
+
+#[constant]
+pub const PAUSE_AIRDROP: u8 = 1 << 1;
+
+#[constant]
+pub const PAUSE_INIT_DAO: u8 = 1 << 2;
+
+#[constant]
+pub const PAUSE_INIT_LP: u8 = 1 << 3;
+
+/// Covers both presales and auctions.
+#[constant]
+pub const PAUSE_SALE: u8 = 1 << 4;
+
 #[program]
 pub mod tatami_v2 {
-    use anchor_spl::metadata::mpl_token_metadata::types::{DataV2, Creator};
+    use anchor_spl::metadata::mpl_token_metadata::types::{DataV2, Creator, Collection};
 
     use super::*;
 
     pub fn create_config(ctx: Context<CreateConfig>, fee: u64) -> Result<()> {
         let config = &mut ctx.accounts.config;
 
+        config.version = CONFIG_VERSION;
         config.creator = ctx.accounts.signer.key();
         config.fee = fee;
         config.bump = ctx.bumps.config;
         Ok(())
     }
 
-    pub fn init_project(
-        ctx: Context<InitProject>,
-        _decimals: u8,
-        name: String, 
-        symbol: String, 
-        uri: String,
-        recipients: u16,
-        supply: [u64; 2]
+    pub fn update_config(ctx: Context<UpdateConfig>, fee: u64) -> Result<()> {
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::UpdateConfig { fee }
+        )?;
+
+        let config = &mut ctx.accounts.config;
+
+        config.fee = fee;
+        Ok(())
+    }
+
+    pub fn set_fee_schedule(ctx: Context<UpdateConfig>, schedule: FeeSchedule) -> Result<()> {
+        require_gte!(MAX_BASIS_POINTS, schedule.holder_discount_bps, Errors::InvalidBasisPoints);
+
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::SetFeeSchedule { schedule: schedule.clone() }
+        )?;
+
+        let config = &mut ctx.accounts.config;
+
+        config.dao_fee = schedule.dao_fee;
+        config.lp_fee = schedule.lp_fee;
+        config.vesting_fee = schedule.vesting_fee;
+        config.holder_discount_mint = schedule.holder_discount_mint;
+        config.holder_discount_min_balance = schedule.holder_discount_min_balance;
+        config.holder_discount_bps = schedule.holder_discount_bps;
+        Ok(())
+    }
+
+    /// Any single admin may add pauses; lifting one needs the full approval.
+    pub fn set_pause(ctx: Context<UpdateConfig>, paused: bool, pause_flags: u8) -> Result<()> {
+        let current = &ctx.accounts.config;
+        let tightens = (paused || !current.paused) && pause_flags & current.pause_flags == current.pause_flags;
+
+        if !(tightens && current.is_admin(&ctx.accounts.signer.key())) {
+            ctx.accounts.config.authorize(
+                &ctx.accounts.signer.key(),
+                ctx.accounts.proposal.as_deref_mut(),
+                AdminAction::SetPause { paused, pause_flags }
+            )?;
+        }
+
+        let config = &mut ctx.accounts.config;
+
+        config.paused = paused;
+        config.pause_flags = pause_flags;
+        Ok(())
+    }
+
+    /// Replaces the admin set. With no admins the config creator acts
+    /// alone; otherwise admin actions need `threshold` approvals.
+    pub fn set_admins(ctx: Context<UpdateConfig>, admins: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        require_gte!(MAX_ADMINS, admins.len(), Errors::InvalidAdminSet);
+        require!(
+            if admins.is_empty() { threshold == 0 } else { threshold > 0 && threshold as usize <= admins.len() },
+            Errors::InvalidAdminSet
+        );
+        for (i, admin) in admins.iter().enumerate() {
+            require!(!admins[..i].contains(admin), Errors::InvalidAdminSet);
+        }
+
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::SetAdmins { admins: admins.clone(), threshold }
+        )?;
+
+        let config = &mut ctx.accounts.config;
+
+        config.admins = [Pubkey::default(); MAX_ADMINS];
+        config.admins[..admins.len()].copy_from_slice(&admins);
+        config.admin_count = admins.len() as u8;
+        config.threshold = threshold;
+        config.admin_epoch += 1;
+        Ok(())
+    }
+
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: AdminAction) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let index = config.admin_index(&ctx.accounts.signer.key()).ok_or(Errors::Unauthorized)?;
+
+        let proposal = &mut ctx.accounts.proposal;
+
+        proposal.id = config.proposal_count;
+        proposal.proposer = ctx.accounts.signer.key();
+        proposal.action = action;
+        proposal.approvals = 1 << index;
+        proposal.admin_epoch = config.admin_epoch;
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+
+        config.proposal_count += 1;
+        Ok(())
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let index = config.admin_index(&ctx.accounts.signer.key()).ok_or(Errors::Unauthorized)?;
+
+        let proposal = &mut ctx.accounts.proposal;
+
+        require!(!proposal.executed, Errors::ProposalExecuted);
+        require_eq!(proposal.admin_epoch, config.admin_epoch, Errors::ProposalStale);
+
+        proposal.approvals |= 1 << index;
+        Ok(())
+    }
+
+    pub fn close_proposal(_ctx: Context<CloseProposal>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn set_fee_override(
+        ctx: Context<SetFeeOverride>,
+        creator: Pubkey,
+        discount_bps: u16,
+        waived: bool
     ) -> Result<()> {
+        require_gte!(MAX_BASIS_POINTS, discount_bps, Errors::InvalidBasisPoints);
+
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::SetFeeOverride { creator, discount_bps, waived }
+        )?;
+
+        let fee_override = &mut ctx.accounts.fee_override;
+
+        fee_override.creator = creator;
+        fee_override.discount_bps = discount_bps;
+        fee_override.waived = waived;
+        fee_override.bump = ctx.bumps.fee_override;
+        Ok(())
+    }
+
+    pub fn remove_fee_override(ctx: Context<RemoveFeeOverride>, creator: Pubkey) -> Result<()> {
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::RemoveFeeOverride { creator }
+        )?;
+        Ok(())
+    }
+
+    pub fn set_referral(ctx: Context<SetReferral>, referrer: Pubkey, share_bps: u16) -> Result<()> {
+        require_gte!(MAX_BASIS_POINTS, share_bps, Errors::InvalidBasisPoints);
+
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::SetReferral { referrer, share_bps }
+        )?;
+
+        let referral = &mut ctx.accounts.referral;
+
+        referral.referrer = referrer;
+        referral.share_bps = share_bps;
+        referral.bump = ctx.bumps.referral;
+        Ok(())
+    }
+
+    pub fn remove_referral(ctx: Context<RemoveReferral>, referrer: Pubkey) -> Result<()> {
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::RemoveReferral { referrer }
+        )?;
+        Ok(())
+    }
+
+    /// Opens the running total of a referrer's shares of fees paid in a fee
+    /// mint. Anyone can open it; launches paying that referrer in that mint
+    /// need it.
+    pub fn init_referral_earnings(ctx: Context<InitReferralEarnings>) -> Result<()> {
+        let earnings = &mut ctx.accounts.referral_earnings;
+
+        earnings.referrer = ctx.accounts.referral.referrer;
+        earnings.mint = ctx.accounts.fee_mint.mint;
+        earnings.bump = ctx.bumps.referral_earnings;
+        Ok(())
+    }
+
+    pub fn set_allowlist(ctx: Context<UpdateConfig>, enabled: bool, verifier: Pubkey) -> Result<()> {
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::SetAllowlist { enabled, verifier }
+        )?;
+
+        let config = &mut ctx.accounts.config;
+
+        config.allowlist_enabled = enabled;
+        config.verifier = verifier;
+        Ok(())
+    }
+
+    /// Lets `initialize_lp` call `program`, driving it as `kind`. Approving a
+    /// program again changes its kind.
+    pub fn approve_amm(ctx: Context<UpdateConfig>, program: Pubkey, kind: AmmKind) -> Result<()> {
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::ApproveAmm { program, kind }
+        )?;
+
+        let config = &mut ctx.accounts.config;
+        let count = config.amm_count as usize;
+
+        match config.amm_programs[..count].iter().position(|amm| amm.program == program) {
+            Some(index) => config.amm_programs[index].kind = kind,
+            None => {
+                require_gt!(MAX_AMM_PROGRAMS, count, Errors::TooManyAmmPrograms);
+
+                config.amm_programs[count] = ApprovedAmm { program, kind };
+                config.amm_count += 1;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn revoke_amm(ctx: Context<UpdateConfig>, program: Pubkey) -> Result<()> {
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::RevokeAmm { program }
+        )?;
+
+        let config = &mut ctx.accounts.config;
+        let count = config.amm_count as usize;
+        let index = config.amm_programs[..count]
+            .iter()
+            .position(|amm| amm.program == program)
+            .ok_or(Errors::AmmNotApproved)?;
+
+        config.amm_programs[index] = config.amm_programs[count - 1];
+        config.amm_programs[count - 1] = ApprovedAmm::default();
+        config.amm_count -= 1;
+        Ok(())
+    }
+
+    pub fn grant_creator_permit(ctx: Context<GrantCreatorPermit>, creator: Pubkey, expires_at: i64) -> Result<()> {
+        if expires_at != 0 {
+            require_gt!(expires_at, Clock::get()?.unix_timestamp, Errors::InvalidDeadline);
+        }
+
+        if !ctx.accounts.config.is_verifier(&ctx.accounts.signer.key()) {
+            ctx.accounts.config.authorize(
+                &ctx.accounts.signer.key(),
+                ctx.accounts.proposal.as_deref_mut(),
+                AdminAction::GrantCreatorPermit { creator, expires_at }
+            )?;
+        }
+
+        let permit = &mut ctx.accounts.permit;
+
+        permit.creator = creator;
+        permit.expires_at = expires_at;
+        permit.issuer = ctx.accounts.signer.key();
+        permit.revoked = false;
+        permit.bump = ctx.bumps.permit;
+        Ok(())
+    }
+
+    /// Lets a creator record a permit signed off-chain by the config's
+    /// verifier. The previous instruction must be an ed25519 signature check
+    /// of `permit_message(creator, nonce, expires_at)` by the verifier, with
+    /// the permit's current nonce (0 before it exists).
+    pub fn claim_creator_permit(ctx: Context<ClaimCreatorPermit>, expires_at: i64) -> Result<()> {
+        if expires_at != 0 {
+            require_gt!(expires_at, Clock::get()?.unix_timestamp, Errors::PermitExpired);
+        }
+
+        let verifier = ctx.accounts.config.verifier;
+        let creator = ctx.accounts.signer.key();
+
+        require_keys_neq!(verifier, Pubkey::default(), Errors::InvalidAttestation);
+        verify_attestation(
+            &ctx.accounts.instructions.to_account_info(),
+            &verifier,
+            &permit_message(&creator, ctx.accounts.permit.nonce, expires_at)
+        )?;
+
+        let permit = &mut ctx.accounts.permit;
+
+        permit.creator = creator;
+        permit.expires_at = expires_at;
+        permit.issuer = verifier;
+        permit.revoked = false;
+        permit.bump = ctx.bumps.permit;
+        Ok(())
+    }
+
+    /// Keeps the permit as a revoked tombstone rather than closing it, so
+    /// the nonce it moves on outlives it and old attestations stay spent.
+    pub fn revoke_creator_permit(ctx: Context<RevokeCreatorPermit>, creator: Pubkey) -> Result<()> {
+        if !ctx.accounts.config.is_verifier(&ctx.accounts.signer.key()) {
+            ctx.accounts.config.authorize(
+                &ctx.accounts.signer.key(),
+                ctx.accounts.proposal.as_deref_mut(),
+                AdminAction::RevokeCreatorPermit { creator }
+            )?;
+        }
+
+        let permit = &mut ctx.accounts.permit;
+
+        permit.revoked = true;
+        permit.nonce += 1;
+        Ok(())
+    }
+
+    pub fn add_fee_mint(ctx: Context<AddFeeMint>, price: u64) -> Result<()> {
+        let mint = ctx.accounts.mint.key();
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::AddFeeMint { mint, price }
+        )?;
+
+        let fee_mint = &mut ctx.accounts.fee_mint;
+
+        fee_mint.mint = mint;
+        fee_mint.price = price;
+        fee_mint.enabled = true;
+        fee_mint.bump = ctx.bumps.fee_mint;
+        Ok(())
+    }
+
+    pub fn update_fee_mint(ctx: Context<UpdateFeeMint>, price: u64, enabled: bool) -> Result<()> {
+        let mint = ctx.accounts.fee_mint.mint;
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::UpdateFeeMint { mint, price, enabled }
+        )?;
+
+        let fee_mint = &mut ctx.accounts.fee_mint;
+
+        fee_mint.price = price;
+        fee_mint.enabled = enabled;
+        Ok(())
+    }
+
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        let destination = ctx.accounts.destination.key();
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::WithdrawFees { destination, amount }
+        )?;
+
+        let seeds: &[u8] = b"tatami-vault";
+
+        let (_, bump) = Pubkey::find_program_address(&[seeds], &id());
+
+        transfer(ctx.accounts.transfer_sol_ctx().with_signer(&[&[seeds, &[bump]]]), amount)?;
+        Ok(())
+    }
+
+    pub fn withdraw_token_fees(ctx: Context<WithdrawTokenFees>, amount: u64) -> Result<()> {
+        let mint = ctx.accounts.mint.key();
+        let destination = ctx.accounts.destination.key();
+        ctx.accounts.config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::WithdrawTokenFees { mint, destination, amount }
+        )?;
+
+        let decimals = ctx.accounts.mint.decimals;
+        let bump = ctx.accounts.config.bump;
+
+        transfer_checked(
+            ctx.accounts.transfer_tokens_ctx().with_signer(&[&[b"tatami-config", &[bump]]]),
+            amount,
+            decimals
+        )?;
+        Ok(())
+    }
+
+    pub fn init_project(ctx: Context<InitProject>, args: InitProjectArgs) -> Result<()> {
+        let InitProjectArgs {
+            decimals,
+            name,
+            symbol,
+            uri,
+            recipients,
+            supply,
+            dao_allocation,
+            airdrop_deadline,
+            clawback_policy,
+            features,
+            creators,
+            collection
+        } = args;
+
+        ctx.accounts.config.require_active(PAUSE_INIT_PROJECT)?;
+        ctx.accounts.check_permit()?;
+        require_gte!(MAX_DECIMALS, decimals, Errors::InvalidDecimals);
+
+        if airdrop_deadline != 0 {
+            require_gt!(airdrop_deadline, Clock::get()?.unix_timestamp, Errors::InvalidDeadline);
+        }
+
+        if dao_allocation > 0 {
+            require!(features & FEATURE_DAO != 0, Errors::FeatureNotPaid);
+        }
+
          // create metadata
-         let creator = Creator {
-            address: ctx.accounts.signer.key(),
-            verified: true,
-            share: 100
+        let signer = ctx.accounts.signer.key();
+        let creators = if creators.is_empty() {
+            vec![CreatorShare { address: signer, share: 100 }]
+        } else {
+            validate_creators(&creators)?;
+            creators
         };
 
+        // only the signer can be verified now, co-creators call verify_creator
+        let creators = creators.into_iter()
+            .map(|creator| Creator {
+                address: creator.address,
+                verified: creator.address == signer,
+                share: creator.share
+            })
+            .collect();
+
         let data = DataV2 {
             name,
             symbol,
             uri,
             seller_fee_basis_points: 0,
-            creators: Some(vec![creator]),
-            collection: None,
+            creators: Some(creators),
+            collection: collection.map(|key| Collection { verified: false, key }),
             uses: None
         };
 
@@ -75,17 +561,103 @@ pub mod tatami_v2 {
         mint_to(ctx.accounts.mint_supply_ctx(false), supply[1])?; // Rest
         
         // Transfer Fee to Vault
-        let fee = ctx.accounts.config.fee;
-        transfer(ctx.accounts.transfer_sol_ctx(), fee)?;
+        let fee = ctx.accounts.launch_fee(features)?;
+
+        let (referrer, share) = ctx.accounts.referral_share(fee)?;
+
+        let (fee, fee_mint, share) = if ctx.accounts.fee_mint.is_some() {
+            ctx.accounts.pay_fee_in_tokens(fee, share)?
+        } else {
+            if share > 0 {
+                ctx.accounts.pay_referrer(share)?;
+            }
+            if fee > share {
+                transfer(ctx.accounts.transfer_sol_ctx(), fee - share)?;
+            }
+            (fee, Pubkey::default(), share)
+        };
+
+        if let Some(referral) = ctx.accounts.referral.as_mut() {
+            referral.projects_referred += 1;
+
+            // Token shares were checked against `referral_earnings` when paid,
+            // it can only be missing when the share rounded down to zero.
+            let earned = if fee_mint == Pubkey::default() {
+                referral.lamports_earned += share;
+                referral.lamports_earned
+            } else if let Some(earnings) = ctx.accounts.referral_earnings.as_mut() {
+                earnings.amount += share;
+                earnings.amount
+            } else {
+                0
+            };
+
+            emit!(ReferralPaid {
+                referrer,
+                project: ctx.accounts.project.key(),
+                fee_mint,
+                amount: share,
+                projects_referred: referral.projects_referred,
+                earned
+            });
+        }
         
         let project = &mut ctx.accounts.project;
 
+        project.version = PROJECT_VERSION;
         project.creator = ctx.accounts.signer.key();
         project.mint = ctx.accounts.mint.key();
         project.bump = ctx.bumps.project;
         project.mint_exist = true;
         project.recipients = recipients;
         project.recipients_paid = 0;
+        project.state = ProjectState::Created;
+        project.dao_allocation = dao_allocation;
+        project.airdrop_deadline = airdrop_deadline;
+        project.clawback_policy = clawback_policy;
+        project.features = features;
+        project.fee_paid = fee;
+        project.fee_mint = fee_mint;
+        project.referrer = referrer;
+        project.registered = false;
+
+        // Only the project mints from here on, within `dao_allocation`.
+        let authority = project.key();
+        set_authority(ctx.accounts.set_auth_ctx(), AuthorityType::MintTokens, Some(authority))?;
+        Ok(())
+    }
+
+    /// Adds a project to the launch registry. Anyone may pay for it, ids are
+    /// handed out in the order projects are registered. Kept out of
+    /// `init_project` so launches don't all write-lock the config.
+    /// `page` is the first page of the creator's list with room left: a
+    /// page is only started once the one before it is full.
+    pub fn register_project(ctx: Context<RegisterProject>, page: u16) -> Result<()> {
+        if page > 0 {
+            let previous = ctx.accounts.previous_page.as_ref().ok_or(Errors::InvalidRegistryPage)?;
+            require_eq!(previous.projects.len(), MAX_CREATOR_PROJECTS, Errors::InvalidRegistryPage);
+        }
+
+        let project = &mut ctx.accounts.project;
+
+        project.id = ctx.accounts.config.next_project_id();
+        project.registered = true;
+
+        let index = &mut ctx.accounts.project_index;
+
+        index.id = project.id;
+        index.project = project.key();
+        index.mint = project.mint;
+        index.bump = ctx.bumps.project_index;
+
+        ctx.accounts.creator_registry.append(project.creator, ctx.bumps.creator_registry, project.id)?;
+
+        emit!(ProjectRegistered {
+            id: project.id,
+            project: project.key(),
+            mint: project.mint,
+            creator: project.creator
+        });
         Ok(())
     }
 
@@ -98,29 +670,110 @@ pub mod tatami_v2 {
         quorum: u8,
         vote_duration: u32
     ) -> Result<()> {
+        ctx.accounts.config.require_active(PAUSE_INIT_DAO)?;
+        require!(ctx.accounts.project.features & FEATURE_DAO != 0, Errors::FeatureNotPaid);
+        require_gte!(ctx.accounts.project.dao_allocation, supply, Errors::DaoAllocationExceeded);
+        ctx.accounts.project.transition(ProjectState::DaoLive)?;
+
         ctx.accounts.create_realm(name, min_vote_to_govern, is_council)?;
         ctx.accounts.create_governance(vote_duration, quorum, min_vote_to_govern)?;
         ctx.accounts.create_native_treasury()?;
         ctx.accounts.set_realm_authority()?;
-        mint_to(ctx.accounts.mint_dao_allocation(), supply)?;
+
+        let mint = ctx.accounts.mint.key();
+        let bump = ctx.accounts.project.bump;
+
+        mint_to(
+            ctx.accounts.mint_dao_allocation().with_signer(&[&[b"tatami-project", mint.as_ref(), &[bump]]]),
+            supply
+        )?;
 
         let project = &mut ctx.accounts.project;
         project.dao_init = true;
+        project.treasury = ctx.accounts.native_treasury.key();
         Ok(())
     }
 
-    pub fn initialize_lp(ctx:Context<InitializeLp>) -> Result<()> {
-        // create_market
+    /// Opens a pool for the token on an approved AMM. `token_amount` comes
+    /// out of the vault and the quote side from the creator; the AMM's own
+    /// accounts follow as remaining accounts, checked by its adapter.
+    pub fn initialize_lp<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeLp<'info>>,
+        args: SeedLiquidityArgs
+    ) -> Result<()> {
+        ctx.accounts.config.require_active(PAUSE_INIT_LP)?;
+
+        let project = &ctx.accounts.project;
+        project.require_open()?;
 
+        require!(project.features & FEATURE_LP != 0, Errors::FeatureNotPaid);
+        require!(!project.lp_init, Errors::LpAlreadyInitialized);
+        require_gt!(args.token_amount, 0, Errors::InvalidAmount);
+
+        let available = project.available_tokens(ctx.accounts.vault_token_account.amount);
+        require_gte!(available, args.token_amount, Errors::InsufficientVaultBalance);
+
+        let amm_program = ctx.accounts.amm_program.to_account_info();
+        let kind = ctx.accounts.config.approved_amm(amm_program.key).ok_or(Errors::AmmNotApproved)?;
+
+        let bump = ctx.bumps.vault;
+
+        transfer_checked(
+            ctx.accounts.transfer_tokens_ctx().with_signer(&[&[b"tatami-vault", &[bump]]]),
+            args.token_amount,
+            ctx.accounts.mint.decimals
+        )?;
+
+        let creator = ctx.accounts.signer.key();
+        let creator_token_account = ctx.accounts.creator_token_account.key();
+        let mint = ctx.accounts.mint.key();
+
+        let (pool, lp_mint) = match kind {
+            AmmKind::CpSwap => seed_liquidity::<CpSwap>(
+                &amm_program,
+                ctx.remaining_accounts,
+                &creator,
+                &creator_token_account,
+                &mint,
+                &args
+            )?
+        };
+
+        let project = &mut ctx.accounts.project;
+        project.lp_init = true;
+        project.lp_mint = lp_mint;
+        project.pool = pool;
+
+        emit!(LiquiditySeeded {
+            project: project.key(),
+            amm_program: amm_program.key(),
+            pool,
+            lp_mint,
+            token_amount: args.token_amount,
+            quote_amount: args.quote_amount
+        });
         Ok(())
     }
 
     pub fn airdrop_tokens(ctx: Context<AirdropTokens>, amount: u64) -> Result<()> {
+        ctx.accounts.config.require_active(PAUSE_AIRDROP)?;
+        ctx.accounts.check_operator()?;
+        ctx.accounts.project.require_open()?;
+
         let decimals = ctx.accounts.mint.decimals;
         let recipients = ctx.accounts.project.recipients;
         let recipients_paid = ctx.accounts.project.recipients_paid;
 
         require_gt!(recipients, recipients_paid, Errors::MaxRecipientsPaid);
+        require!(!ctx.accounts.project.airdrop_closed, Errors::AirdropClosed);
+
+        let deadline = ctx.accounts.project.airdrop_deadline;
+        if deadline != 0 {
+            require_gte!(deadline, Clock::get()?.unix_timestamp, Errors::AirdropEnded);
+        }
+
+        let available = ctx.accounts.project.available_tokens(ctx.accounts.vault_token_account.amount);
+        require_gte!(available, amount, Errors::InsufficientVaultBalance);
 
         let seeds: &[u8] = b"tatami-vault";
 
@@ -134,54 +787,2533 @@ pub mod tatami_v2 {
 
         let project = &mut ctx.accounts.project;
         project.recipients_paid += 1;
+
+        if project.state == ProjectState::Created {
+            project.transition(ProjectState::Distributed)?;
+        }
         Ok(())
     }
 
-    pub fn burn_authority(ctx: Context<BurnAuthority>) -> Result<()> {
-        set_authority(ctx.accounts.set_auth_ctx(), AuthorityType::MintTokens, None)?;
+    pub fn set_airdrop_deadline(ctx: Context<SetAirdropDeadline>, airdrop_deadline: i64) -> Result<()> {
         let project = &mut ctx.accounts.project;
-        project.mint_exist = false;
+
+        project.require_open()?;
+        require_eq!(project.airdrop_deadline, 0, Errors::AirdropDeadlineSet);
+        require_gt!(airdrop_deadline, Clock::get()?.unix_timestamp, Errors::InvalidDeadline);
+
+        project.airdrop_deadline = airdrop_deadline;
         Ok(())
     }
-}
 
+    pub fn close_airdrop(ctx: Context<CloseAirdrop>) -> Result<()> {
+        let project = &ctx.accounts.project;
 
-#[derive(Accounts)]
-pub struct CreateConfig<'info> {
-    #[account(
-        init, 
-        payer = signer, 
-        space = 8 + 8 + 32 + 1,
-        seeds = [b"tatami-config"],
-        bump
-    )]
-    pub config: Account<'info, Config>,
+        require!(!project.airdrop_closed, Errors::AirdropClosed);
+        require_neq!(project.airdrop_deadline, 0, Errors::NoAirdropDeadline);
+        require_gt!(Clock::get()?.unix_timestamp, project.airdrop_deadline, Errors::AirdropNotEnded);
+
+        let decimals = ctx.accounts.mint.decimals;
+        let amount = project.available_tokens(ctx.accounts.vault_token_account.amount);
+
+        let seeds: &[u8] = b"tatami-vault";
+
+        let (_, bump) = Pubkey::find_program_address(&[seeds], &id());
+        let signer: &[&[&[u8]]] = &[&[seeds, &[bump]]];
+
+        if amount > 0 {
+            match project.clawback_policy {
+                ClawbackPolicy::Creator => {
+                    let to = ctx.accounts.creator_token_account.as_ref()
+                        .ok_or(Errors::MissingClawbackAccount)?
+                        .to_account_info();
+
+                    transfer_checked(ctx.accounts.transfer_tokens_ctx(to).with_signer(signer), amount, decimals)?;
+                },
+                ClawbackPolicy::Treasury => {
+                    require!(project.dao_init, Errors::DaoNotInitialized);
+
+                    let to = ctx.accounts.dao_token_account.as_ref()
+                        .ok_or(Errors::MissingClawbackAccount)?
+                        .to_account_info();
+
+                    transfer_checked(ctx.accounts.transfer_tokens_ctx(to).with_signer(signer), amount, decimals)?;
+                },
+                ClawbackPolicy::Burn => {
+                    burn(ctx.accounts.burn_tokens_ctx().with_signer(signer), amount)?;
+                }
+            }
+        }
+
+        let project = &mut ctx.accounts.project;
+        project.airdrop_closed = true;
+        project.clawed_back = amount;
+
+        // Closing an airdrop nobody was paid from still ends distribution.
+        if project.state == ProjectState::Created {
+            project.transition(ProjectState::Distributed)?;
+        }
+
+        if amount > 0 && project.clawback_policy == ClawbackPolicy::Burn {
+            project.burned += amount;
+
+            emit!(TokensBurned {
+                project: project.key(),
+                mint: project.mint,
+                authority: ctx.accounts.signer.key(),
+                amount,
+                total_burned: project.burned,
+                timestamp: Clock::get()?.unix_timestamp
+            });
+        }
+        Ok(())
+    }
+
+    /// Allowed in every state: burning only shrinks supply, and it's how a
+    /// finalized project empties its vault before `close_project`.
+    pub fn burn_vault_tokens(ctx: Context<BurnVaultTokens>, amount: u64) -> Result<()> {
+        require_gt!(amount, 0, Errors::InvalidAmount);
+
+        let available = ctx.accounts.project.available_tokens(ctx.accounts.vault_token_account.amount);
+        require_gte!(available, amount, Errors::InsufficientVaultBalance);
+
+        let seeds: &[u8] = b"tatami-vault";
+
+        let (_, bump) = Pubkey::find_program_address(&[seeds], &id());
+
+        burn(ctx.accounts.burn_tokens_ctx().with_signer(&[&[seeds, &[bump]]]), amount)?;
+
+        let project = &mut ctx.accounts.project;
+        project.burned += amount;
+
+        emit!(TokensBurned {
+            project: project.key(),
+            mint: project.mint,
+            authority: ctx.accounts.signer.key(),
+            amount,
+            total_burned: project.burned,
+            timestamp: Clock::get()?.unix_timestamp
+        });
+        Ok(())
+    }
+
+    /// Only once the airdrop is over, so recipients still owed tokens aren't
+    /// left with an empty vault.
+    pub fn sweep_to_treasury(ctx: Context<SweepToTreasury>) -> Result<()> {
+        require!(ctx.accounts.project.dao_init, Errors::DaoNotInitialized);
+        require!(ctx.accounts.project.airdrop_ended(Clock::get()?.unix_timestamp), Errors::AirdropNotEnded);
+
+        let decimals = ctx.accounts.mint.decimals;
+        let amount = ctx.accounts.project.available_tokens(ctx.accounts.vault_token_account.amount);
+
+        require_gt!(amount, 0, Errors::VaultEmpty);
+
+        let seeds: &[u8] = b"tatami-vault";
+
+        let (_, bump) = Pubkey::find_program_address(&[seeds], &id());
+
+        transfer_checked(
+            ctx.accounts.transfer_tokens_ctx().with_signer(&[&[seeds, &[bump]]]),
+            amount,
+            decimals
+        )?;
+
+        let project = &mut ctx.accounts.project;
+        project.swept += amount;
+        Ok(())
+    }
+
+    /// Opens a presale of `args.token_amount` vault tokens, which stay
+    /// reserved until contributors claim them or the sale is closed.
+    pub fn create_sale(ctx: Context<CreateSale>, args: SaleArgs) -> Result<()> {
+        ctx.accounts.config.require_active(PAUSE_SALE)?;
+        ctx.accounts.project.require_open()?;
+        args.validate(Clock::get()?.unix_timestamp)?;
+
+        let project = &mut ctx.accounts.project;
+        let available = project.available_tokens(ctx.accounts.vault_token_account.amount);
+
+        require_gte!(available, args.token_amount, Errors::InsufficientVaultBalance);
+        project.reserved_tokens += args.token_amount;
+
+        let sale = &mut ctx.accounts.sale;
+
+        sale.project = project.key();
+        sale.start = args.start;
+        sale.end = args.end;
+        sale.soft_cap = args.soft_cap;
+        sale.hard_cap = args.hard_cap;
+        sale.min_contribution = args.min_contribution;
+        sale.max_contribution = args.max_contribution;
+        sale.merkle_root = args.merkle_root;
+        sale.token_amount = args.token_amount;
+        sale.bump = ctx.bumps.sale;
+        Ok(())
+    }
+
+    /// `proof` is only read when the sale has an allowlist.
+    pub fn contribute(ctx: Context<Contribute>, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        ctx.accounts.config.require_active(PAUSE_SALE)?;
+        ctx.accounts.project.require_open()?;
+        require_gt!(amount, 0, Errors::InvalidAmount);
+
+        let signer = ctx.accounts.signer.key();
+        let sale = &ctx.accounts.sale;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(now >= sale.start && !sale.has_ended(now), Errors::SaleNotActive);
+
+        if sale.merkle_root != [0; 32] {
+            require!(verify_allowlist_proof(&proof, &sale.merkle_root, &signer), Errors::NotOnAllowlist);
+        }
+
+        require_gte!(sale.hard_cap - sale.total_raised, amount, Errors::SaleHardCapReached);
+
+        let total = ctx.accounts.contribution.amount + amount;
+
+        require_gte!(total, sale.min_contribution, Errors::InvalidContribution);
+        if sale.max_contribution > 0 {
+            require_gte!(sale.max_contribution, total, Errors::InvalidContribution);
+        }
+
+        transfer(ctx.accounts.transfer_sol_ctx(), amount)?;
+
+        let sale = &mut ctx.accounts.sale;
+        let contribution = &mut ctx.accounts.contribution;
+
+        if contribution.amount == 0 {
+            sale.contributors += 1;
+            contribution.sale = sale.key();
+            contribution.contributor = signer;
+            contribution.bump = ctx.bumps.contribution;
+        }
+
+        contribution.amount = total;
+        sale.total_raised += amount;
+        Ok(())
+    }
+
+    /// Pays a contributor their pro-rata share of a successful sale.
+    pub fn claim_sale_tokens(ctx: Context<ClaimSaleTokens>) -> Result<()> {
+        let sale = &ctx.accounts.sale;
+
+        require!(sale.has_ended(Clock::get()?.unix_timestamp), Errors::SaleNotEnded);
+        require!(sale.succeeded(), Errors::SoftCapNotMet);
+
+        let tokens = sale.allocation(ctx.accounts.contribution.amount);
+        let decimals = ctx.accounts.mint.decimals;
+
+        let seeds: &[u8] = b"tatami-vault";
+
+        let (_, bump) = Pubkey::find_program_address(&[seeds], &id());
+
+        if tokens > 0 {
+            transfer_checked(
+                ctx.accounts.transfer_tokens_ctx().with_signer(&[&[seeds, &[bump]]]),
+                tokens,
+                decimals
+            )?;
+        }
+
+        let sale = &mut ctx.accounts.sale;
+        sale.tokens_claimed += tokens;
+        sale.settled += 1;
+
+        ctx.accounts.project.reserved_tokens -= tokens;
+        Ok(())
+    }
+
+    /// Returns a contribution in full when the sale missed its soft cap.
+    pub fn refund_contribution(ctx: Context<RefundContribution>) -> Result<()> {
+        let sale = &ctx.accounts.sale;
+
+        require!(sale.has_ended(Clock::get()?.unix_timestamp), Errors::SaleNotEnded);
+        require!(!sale.succeeded(), Errors::SoftCapMet);
+
+        withdraw_lamports(
+            &ctx.accounts.sale.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            ctx.accounts.contribution.amount
+        )?;
+
+        ctx.accounts.sale.settled += 1;
+        Ok(())
+    }
+
+    pub fn withdraw_sale_proceeds(ctx: Context<WithdrawSaleProceeds>) -> Result<()> {
+        let sale = &ctx.accounts.sale;
+
+        require!(sale.has_ended(Clock::get()?.unix_timestamp), Errors::SaleNotEnded);
+        require!(sale.succeeded(), Errors::SoftCapNotMet);
+        require!(!sale.proceeds_withdrawn, Errors::SaleProceedsWithdrawn);
+
+        withdraw_lamports(
+            &ctx.accounts.sale.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            sale.total_raised
+        )?;
+
+        ctx.accounts.sale.proceeds_withdrawn = true;
+        Ok(())
+    }
+
+    /// Releases unsold tokens back to the vault once every contribution is
+    /// claimed or refunded. Unwithdrawn proceeds go to the creator with the rent.
+    pub fn close_sale(ctx: Context<CloseSale>) -> Result<()> {
+        let sale = &ctx.accounts.sale;
+
+        require!(sale.has_ended(Clock::get()?.unix_timestamp), Errors::SaleNotEnded);
+        require_eq!(sale.settled, sale.contributors, Errors::SaleNotSettled);
+
+        ctx.accounts.project.reserved_tokens -= sale.token_amount - sale.tokens_claimed;
+        Ok(())
+    }
+
+    /// Opens a descending-price auction of `args.token_amount` vault tokens.
+    pub fn create_auction(ctx: Context<CreateAuction>, args: AuctionArgs) -> Result<()> {
+        ctx.accounts.config.require_active(PAUSE_SALE)?;
+        ctx.accounts.project.require_open()?;
+        args.validate(Clock::get()?.unix_timestamp)?;
+
+        let project = &mut ctx.accounts.project;
+        let available = project.available_tokens(ctx.accounts.vault_token_account.amount);
+
+        require_gte!(available, args.token_amount, Errors::InsufficientVaultBalance);
+        project.reserved_tokens += args.token_amount;
+
+        let auction = &mut ctx.accounts.auction;
+
+        auction.project = project.key();
+        auction.start = args.start;
+        auction.end = args.end;
+        auction.start_price = args.start_price;
+        auction.floor_price = args.floor_price;
+        auction.decay_interval = args.decay_interval;
+        auction.token_amount = args.token_amount;
+        auction.decimals = ctx.accounts.mint.decimals;
+        auction.bump = ctx.bumps.auction;
+        Ok(())
+    }
+
+    /// Buys up to `quantity` tokens at the current price, filling whatever is
+    /// left if that's less. The deposit is trued up to the clearing price on claim.
+    pub fn place_bid(ctx: Context<PlaceBid>, quantity: u64) -> Result<()> {
+        ctx.accounts.config.require_active(PAUSE_SALE)?;
+        ctx.accounts.project.require_open()?;
+
+        let auction = &ctx.accounts.auction;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(now >= auction.start && !auction.has_ended(now), Errors::AuctionNotActive);
+
+        let quantity = quantity.min(auction.token_amount - auction.tokens_committed);
+        require_gt!(quantity, 0, Errors::InvalidAmount);
+
+        let price = auction.price_at(now);
+        let deposit = auction.cost(quantity, price)?;
+
+        transfer(ctx.accounts.transfer_sol_ctx(), deposit)?;
+
+        let auction = &mut ctx.accounts.auction;
+        let bid = &mut ctx.accounts.bid;
+
+        if bid.quantity == 0 {
+            auction.bidders += 1;
+            bid.auction = auction.key();
+            bid.bidder = ctx.accounts.signer.key();
+            bid.bump = ctx.bumps.bid;
+        }
+
+        bid.quantity += quantity;
+        bid.deposited += deposit;
+        auction.tokens_committed += quantity;
+        auction.total_deposited += deposit;
+
+        if auction.tokens_committed == auction.token_amount {
+            auction.clearing_price = price;
+        }
+        Ok(())
+    }
+
+    /// Sends a bidder their tokens and refunds what they paid above the
+    /// clearing price.
+    pub fn claim_auction_tokens(ctx: Context<ClaimAuctionTokens>) -> Result<()> {
+        let auction = &ctx.accounts.auction;
+
+        require!(auction.has_ended(Clock::get()?.unix_timestamp), Errors::AuctionNotEnded);
+
+        let bid = &ctx.accounts.bid;
+        let refund = bid.deposited - auction.cost(bid.quantity, auction.final_price())?;
+        let quantity = bid.quantity;
+        let decimals = ctx.accounts.mint.decimals;
+
+        let seeds: &[u8] = b"tatami-vault";
+
+        let (_, bump) = Pubkey::find_program_address(&[seeds], &id());
+
+        transfer_checked(
+            ctx.accounts.transfer_tokens_ctx().with_signer(&[&[seeds, &[bump]]]),
+            quantity,
+            decimals
+        )?;
+
+        if refund > 0 {
+            withdraw_lamports(
+                &ctx.accounts.auction.to_account_info(),
+                &ctx.accounts.signer.to_account_info(),
+                refund
+            )?;
+        }
+
+        let auction = &mut ctx.accounts.auction;
+        auction.tokens_claimed += quantity;
+        auction.settled += 1;
+
+        ctx.accounts.project.reserved_tokens -= quantity;
+        Ok(())
+    }
+
+    /// Pays the raise to the DAO treasury once the DAO is live, otherwise to
+    /// the creator. Anyone may crank it.
+    pub fn withdraw_auction_proceeds(ctx: Context<WithdrawAuctionProceeds>) -> Result<()> {
+        let auction = &ctx.accounts.auction;
+
+        require!(auction.has_ended(Clock::get()?.unix_timestamp), Errors::AuctionNotEnded);
+        require!(!auction.proceeds_withdrawn, Errors::AuctionProceedsWithdrawn);
+
+        withdraw_lamports(
+            &ctx.accounts.auction.to_account_info(),
+            &ctx.accounts.destination.to_account_info(),
+            auction.cost(auction.tokens_committed, auction.final_price())?
+        )?;
+
+        ctx.accounts.auction.proceeds_withdrawn = true;
+        Ok(())
+    }
+
+    /// Releases unsold tokens once every bid is claimed. Rounding dust goes to
+    /// the creator with the rent.
+    pub fn close_auction(ctx: Context<CloseAuction>) -> Result<()> {
+        let auction = &ctx.accounts.auction;
+
+        require!(auction.has_ended(Clock::get()?.unix_timestamp), Errors::AuctionNotEnded);
+        require!(auction.proceeds_withdrawn || auction.tokens_committed == 0, Errors::AuctionNotSettled);
+        require_eq!(auction.settled, auction.bidders, Errors::AuctionNotSettled);
+
+        ctx.accounts.project.reserved_tokens -= auction.token_amount - auction.tokens_claimed;
+        Ok(())
+    }
+
+    /// Escrows LP tokens until `unlock_at`. Calling it again tops the lock up
+    /// or pushes `unlock_at` later, never earlier.
+    pub fn lock_lp(ctx: Context<LockLp>, amount: u64, unlock_at: i64) -> Result<()> {
+        ctx.accounts.project.require_open()?;
+
+        let status = ctx.accounts.project.lp_lock;
+        let lp_lock = &ctx.accounts.lp_lock;
+
+        require!(status != LpLockStatus::Burned, Errors::LpNotLocked);
+        require_gt!(unlock_at, Clock::get()?.unix_timestamp, Errors::InvalidUnlockTime);
+
+        if status == LpLockStatus::Unlocked {
+            require_gt!(amount, 0, Errors::InvalidAmount);
+        } else {
+            require_gte!(unlock_at, lp_lock.unlock_at, Errors::InvalidUnlockTime);
+        }
+
+        if amount > 0 {
+            transfer_checked(ctx.accounts.transfer_lp_ctx(), amount, ctx.accounts.lp_mint.decimals)?;
+        }
+
+        let lp_lock = &mut ctx.accounts.lp_lock;
+
+        lp_lock.project = ctx.accounts.project.key();
+        lp_lock.lp_mint = ctx.accounts.lp_mint.key();
+        lp_lock.amount += amount;
+        lp_lock.unlock_at = unlock_at;
+        lp_lock.bump = ctx.bumps.lp_lock;
+
+        let project = &mut ctx.accounts.project;
+        if project.lp_lock == LpLockStatus::Unlocked {
+            project.lp_lock = LpLockStatus::Locked;
+        }
+
+        emit!(LpLockChanged {
+            project: project.key(),
+            lp_mint: lp_lock.lp_mint,
+            amount: lp_lock.amount,
+            unlock_at,
+            status: project.lp_lock
+        });
+        Ok(())
+    }
+
+    pub fn make_lp_lock_permanent(ctx: Context<UpdateLpLock>) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+
+        require!(project.lp_lock == LpLockStatus::Locked, Errors::LpNotLocked);
+        project.lp_lock = LpLockStatus::Permanent;
+
+        let lp_lock = &ctx.accounts.lp_lock;
+
+        emit!(LpLockChanged {
+            project: project.key(),
+            lp_mint: lp_lock.lp_mint,
+            amount: lp_lock.amount,
+            unlock_at: lp_lock.unlock_at,
+            status: project.lp_lock
+        });
+        Ok(())
+    }
+
+    /// Burns the locked LP tokens for good and closes the lock.
+    pub fn burn_locked_lp(ctx: Context<BurnLockedLp>) -> Result<()> {
+        let status = ctx.accounts.project.lp_lock;
+
+        require!(matches!(status, LpLockStatus::Locked | LpLockStatus::Permanent), Errors::LpNotLocked);
+
+        let project = ctx.accounts.project.key();
+        let seeds: &[&[&[u8]]] = &[&[b"tatami-lp-lock", project.as_ref(), &[ctx.accounts.lp_lock.bump]]];
+
+        burn(ctx.accounts.burn_lp_ctx().with_signer(seeds), ctx.accounts.escrow.amount)?;
+        close_account(ctx.accounts.close_escrow_ctx().with_signer(seeds))?;
+
+        ctx.accounts.project.lp_lock = LpLockStatus::Burned;
+
+        let lp_lock = &ctx.accounts.lp_lock;
+
+        emit!(LpLockChanged {
+            project,
+            lp_mint: lp_lock.lp_mint,
+            amount: 0,
+            unlock_at: lp_lock.unlock_at,
+            status: LpLockStatus::Burned
+        });
+        Ok(())
+    }
+
+    /// Returns the LP tokens to `destination` once `unlock_at` has passed and
+    /// closes the lock.
+    pub fn unlock_lp(ctx: Context<UnlockLp>) -> Result<()> {
+        let lp_lock = &ctx.accounts.lp_lock;
+
+        require!(ctx.accounts.project.lp_lock != LpLockStatus::Permanent, Errors::LpLockPermanent);
+        require!(ctx.accounts.project.lp_lock == LpLockStatus::Locked, Errors::LpNotLocked);
+        require_gte!(Clock::get()?.unix_timestamp, lp_lock.unlock_at, Errors::LpStillLocked);
+
+        let project = ctx.accounts.project.key();
+        let seeds: &[&[&[u8]]] = &[&[b"tatami-lp-lock", project.as_ref(), &[lp_lock.bump]]];
+
+        transfer_checked(
+            ctx.accounts.transfer_lp_ctx().with_signer(seeds),
+            ctx.accounts.escrow.amount,
+            ctx.accounts.lp_mint.decimals
+        )?;
+        close_account(ctx.accounts.close_escrow_ctx().with_signer(seeds))?;
+
+        ctx.accounts.project.lp_lock = LpLockStatus::Unlocked;
+
+        emit!(LpLockChanged {
+            project,
+            lp_mint: lp_lock.lp_mint,
+            amount: 0,
+            unlock_at: lp_lock.unlock_at,
+            status: LpLockStatus::Unlocked
+        });
+        Ok(())
+    }
+
+    /// A planned DAO has to be live first: once the authority is gone its
+    /// allocation could never be minted, and the project never closed.
+    pub fn burn_authority(ctx: Context<BurnAuthority>) -> Result<()> {
+        let project = &ctx.accounts.project;
+        require!(project.dao_allocation == 0 || project.dao_init, Errors::DaoNotInitialized);
+
+        ctx.accounts.project.transition(ProjectState::Finalized)?;
+
+        let mint = ctx.accounts.mint.key();
+        let bump = ctx.accounts.project.bump;
+
+        set_authority(
+            ctx.accounts.set_auth_ctx().with_signer(&[&[b"tatami-project", mint.as_ref(), &[bump]]]),
+            AuthorityType::MintTokens,
+            None
+        )?;
+        let project = &mut ctx.accounts.project;
+        project.mint_exist = false;
+        Ok(())
+    }
+
+    /// Lets a co-creator listed at launch mark themselves verified in the
+    /// token metadata.
+    pub fn verify_creator(ctx: Context<VerifyCreator>) -> Result<()> {
+        sign_metadata(ctx.accounts.sign_metadata_ctx())
+    }
+
+    /// First step of a handover: names the next creator, who must accept.
+    /// The metadata update authority moves to the project until the handover
+    /// is accepted. Pass the default key to cancel.
+    pub fn transfer_project_ownership(ctx: Context<TransferProjectOwnership>, new_creator: Pubkey) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        let project = ctx.accounts.project.key();
+
+        require_keys_neq!(new_creator, signer, Errors::InvalidNewCreator);
+
+        let update_authority = ctx.accounts.metadata.update_authority;
+
+        if new_creator == Pubkey::default() && update_authority == project {
+            let mint = ctx.accounts.mint.key();
+            let seeds: &[&[&[u8]]] = &[&[b"tatami-project", mint.as_ref(), &[ctx.accounts.project.bump]]];
+
+            let ctx_update = ctx.accounts.update_metadata_ctx(ctx.accounts.project.to_account_info());
+            update_metadata_accounts_v2(ctx_update.with_signer(seeds), Some(signer), None, None, None)?;
+        } else if new_creator != Pubkey::default() && update_authority == signer {
+            let ctx_update = ctx.accounts.update_metadata_ctx(ctx.accounts.signer.to_account_info());
+            update_metadata_accounts_v2(ctx_update, Some(project), None, None, None)?;
+        }
+
+        ctx.accounts.project.pending_creator = new_creator;
+        Ok(())
+    }
+
+    pub fn accept_project_ownership(ctx: Context<AcceptProjectOwnership>) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+
+        if ctx.accounts.metadata.update_authority == ctx.accounts.project.key() {
+            let mint = ctx.accounts.project.mint;
+            let seeds: &[&[&[u8]]] = &[&[b"tatami-project", mint.as_ref(), &[ctx.accounts.project.bump]]];
+
+            update_metadata_accounts_v2(
+                ctx.accounts.update_metadata_ctx().with_signer(seeds),
+                Some(signer),
+                None,
+                None,
+                None
+            )?;
+        }
+
+        let project = &mut ctx.accounts.project;
+
+        project.creator = signer;
+        project.pending_creator = Pubkey::default();
+        Ok(())
+    }
+
+    pub fn set_project_role(ctx: Context<SetProjectRole>, operator: Pubkey, roles: u8) -> Result<()> {
+        let role = &mut ctx.accounts.role;
+
+        role.project = ctx.accounts.project.key();
+        role.operator = operator;
+        role.roles = roles;
+        role.granted_by = ctx.accounts.signer.key();
+        role.bump = ctx.bumps.role;
+        Ok(())
+    }
+
+    pub fn remove_project_role(_ctx: Context<RemoveProjectRole>, _operator: Pubkey) -> Result<()> {
+        Ok(())
+    }
+
+    /// Creates or replaces the project's profile as a whole.
+    pub fn set_project_profile(ctx: Context<SetProjectProfile>, data: ProfileData) -> Result<()> {
+        data.validate()?;
+
+        let profile = &mut ctx.accounts.profile;
+
+        profile.project = ctx.accounts.project.key();
+        profile.website = data.website;
+        profile.socials = data.socials;
+        profile.description_hash = data.description_hash;
+        profile.category = data.category;
+        profile.tags = data.tags;
+        profile.updated_at = Clock::get()?.unix_timestamp;
+        profile.bump = ctx.bumps.profile;
+        Ok(())
+    }
+
+    pub fn close_project_profile(_ctx: Context<CloseProjectProfile>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn close_project(ctx: Context<CloseProject>) -> Result<()> {
+        ctx.accounts.check_closable()?;
+
+        let seeds: &[u8] = b"tatami-vault";
+
+        let (_, bump) = Pubkey::find_program_address(&[seeds], &id());
+
+        close_account(ctx.accounts.close_vault_ctx().with_signer(&[&[seeds, &[bump]]]))?;
+        Ok(())
+    }
+
+    /// Mints still held by the creator's wallet hand their authority to the
+    /// project here, which is what every later instruction signs with.
+    pub fn migrate_project(ctx: Context<MigrateProject>) -> Result<()> {
+        let info = ctx.accounts.project.to_account_info();
+
+        let project = {
+            let data = info.try_borrow_data()?;
+            require!(data.len() >= 8 && data[..8] == Project::DISCRIMINATOR, Errors::InvalidAccountVersion);
+
+            if data.len() == LEGACY_PROJECT_LEN {
+                let legacy = ProjectV0::deserialize(&mut &data[8..])?;
+                Project::from_legacy(legacy)
+            } else {
+                let mut project = Project::try_deserialize_padded(&data)?;
+                require_gt!(PROJECT_VERSION, project.version, Errors::AlreadyMigrated);
+                project.version = PROJECT_VERSION;
+                project
+            }
+        };
+
+        require_keys_eq!(ctx.accounts.signer.key(), project.creator, Errors::Unauthorized);
+
+        if ctx.accounts.mint.mint_authority == COption::Some(project.creator) {
+            set_authority(ctx.accounts.set_auth_ctx(), AuthorityType::MintTokens, Some(info.key()))?;
+        }
+
+        realloc_account(
+            &info,
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + Project::INIT_SPACE
+        )?;
+
+        let mut data = info.try_borrow_mut_data()?;
+        project.try_serialize(&mut &mut data[..])?;
+        Ok(())
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let info = ctx.accounts.config.to_account_info();
+
+        let config = {
+            let data = info.try_borrow_data()?;
+            require!(data.len() >= 8 && data[..8] == Config::DISCRIMINATOR, Errors::InvalidAccountVersion);
+
+            if data.len() == LEGACY_CONFIG_LEN {
+                let legacy = ConfigV0::deserialize(&mut &data[8..])?;
+                Config::from_legacy(legacy)
+            } else {
+                let mut config = Config::try_deserialize(&mut &data[..])?;
+                require_gt!(CONFIG_VERSION, config.version, Errors::AlreadyMigrated);
+                config.version = CONFIG_VERSION;
+                config
+            }
+        };
+
+        config.authorize(
+            &ctx.accounts.signer.key(),
+            ctx.accounts.proposal.as_deref_mut(),
+            AdminAction::MigrateConfig
+        )?;
+
+        realloc_account(
+            &info,
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + Config::INIT_SPACE
+        )?;
+
+        let mut data = info.try_borrow_mut_data()?;
+        config.try_serialize(&mut &mut data[..])?;
+        Ok(())
+    }
+}
+
+
+#[derive(Accounts)]
+pub struct CreateConfig<'info> {
+    #[account(
+        init, 
+        payer = signer, 
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"tatami-config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+    pub signer: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [
+            b"tatami-proposal",
+            config.proposal_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub signer: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut, address = proposal.proposer @ Errors::Unauthorized)]
+    pub signer: Signer<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct SetFeeOverride<'info> {
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + FeeOverride::INIT_SPACE,
+        seeds = [
+            b"tatami-fee-override",
+            creator.as_ref()
+        ],
+        bump
+    )]
+    pub fee_override: Account<'info, FeeOverride>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct RemoveFeeOverride<'info> {
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"tatami-fee-override",
+            creator.as_ref()
+        ],
+        bump = fee_override.bump
+    )]
+    pub fee_override: Account<'info, FeeOverride>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+    #[account(mut)]
+    pub signer: Signer<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(referrer: Pubkey)]
+pub struct SetReferral<'info> {
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + Referral::INIT_SPACE,
+        seeds = [
+            b"tatami-referral",
+            referrer.as_ref()
+        ],
+        bump
+    )]
+    pub referral: Account<'info, Referral>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction(referrer: Pubkey)]
+pub struct RemoveReferral<'info> {
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"tatami-referral",
+            referrer.as_ref()
+        ],
+        bump = referral.bump
+    )]
+    pub referral: Account<'info, Referral>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+    #[account(mut)]
+    pub signer: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct InitReferralEarnings<'info> {
+    #[account(
+        seeds = [
+            b"tatami-referral",
+            referral.referrer.as_ref()
+        ],
+        bump = referral.bump
+    )]
+    pub referral: Account<'info, Referral>,
+    #[account(
+        seeds = [
+            b"tatami-fee-mint",
+            fee_mint.mint.as_ref()
+        ],
+        bump = fee_mint.bump
+    )]
+    pub fee_mint: Account<'info, FeeMint>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + ReferralEarnings::INIT_SPACE,
+        seeds = [
+            b"tatami-referral-earnings",
+            referral.referrer.as_ref(),
+            fee_mint.mint.as_ref()
+        ],
+        bump
+    )]
+    pub referral_earnings: Account<'info, ReferralEarnings>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct GrantCreatorPermit<'info> {
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + CreatorPermit::INIT_SPACE,
+        seeds = [
+            b"tatami-permit",
+            creator.as_ref()
+        ],
+        bump
+    )]
+    pub permit: Account<'info, CreatorPermit>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorPermit<'info> {
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + CreatorPermit::INIT_SPACE,
+        seeds = [
+            b"tatami-permit",
+            signer.key().as_ref()
+        ],
+        bump
+    )]
+    pub permit: Account<'info, CreatorPermit>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    /// CHECK: Address checked, read to find the ed25519 signature check
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct RevokeCreatorPermit<'info> {
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-permit",
+            creator.as_ref()
+        ],
+        bump = permit.bump
+    )]
+    pub permit: Account<'info, CreatorPermit>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+    #[account(mut)]
+    pub signer: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct AddFeeMint<'info> {
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + FeeMint::INIT_SPACE,
+        seeds = [
+            b"tatami-fee-mint",
+            mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub fee_mint: Account<'info, FeeMint>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = config
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeeMint<'info> {
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-fee-mint",
+            fee_mint.mint.as_ref()
+        ],
+        bump = fee_mint.bump
+    )]
+    pub fee_mint: Account<'info, FeeMint>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+    pub signer: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-vault"
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(mut)]
+    pub destination: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTokenFees<'info> {
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = config
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = mint
+    )]
+    pub destination: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>
+}
+
+#[derive(Accounts)]
+#[instruction(args: InitProjectArgs)]
+pub struct InitProject<'info> {
+    #[account(
+        init, 
+        payer = signer, 
+        space = 8 + Project::INIT_SPACE,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-vault"
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = signer,
+        mint::decimals = args.decimals,
+        mint::authority = signer
+    )]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = team_wallet
+    )]
+    pub team_token_account: Option<Account<'info, TokenAccount>>,
+    pub team_wallet: Option<SystemAccount<'info>>,
+    #[account(
+        seeds = [
+            b"tatami-fee-override",
+            signer.key().as_ref()
+        ],
+        bump = fee_override.bump
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+    #[account(
+        constraint = discount_token_account.owner == signer.key() @ Errors::DiscountNotEligible,
+        constraint = discount_token_account.mint == config.holder_discount_mint @ Errors::DiscountNotEligible
+    )]
+    pub discount_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    pub fee_mint: Option<Box<Account<'info, FeeMint>>>,
+    pub fee_token_mint: Option<Box<Account<'info, Mint>>>,
+    #[account(mut)]
+    pub payer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub fee_vault: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-referral",
+            referral.referrer.as_ref()
+        ],
+        bump = referral.bump
+    )]
+    pub referral: Option<Box<Account<'info, Referral>>>,
+    /// Wallet paid the referral share of a SOL fee.
+    #[account(mut)]
+    pub referrer: Option<SystemAccount<'info>>,
+    /// Token account paid the referral share of a token fee.
+    #[account(mut)]
+    pub referrer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-referral-earnings",
+            referral_earnings.referrer.as_ref(),
+            referral_earnings.mint.as_ref()
+        ],
+        bump = referral_earnings.bump
+    )]
+    pub referral_earnings: Option<Box<Account<'info, ReferralEarnings>>>,
+    #[account(
+        seeds = [
+            b"tatami-permit",
+            signer.key().as_ref()
+        ],
+        bump = creator_permit.bump
+    )]
+    pub creator_permit: Option<Box<Account<'info, CreatorPermit>>>,
+    /// CHECK: This account is initialized in the ix
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>
+}
+
+#[derive(Accounts)]
+pub struct InitializeDao<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = signer,
+        mint::decimals = 1,
+        mint::authority = signer
+    )]
+    pub council_mint: Option<Account<'info, Mint>>,
+    /// CHECK: CPI Account
+    #[account(mut)]
+    pub realm_account: UncheckedAccount<'info>,
+    /// CHECK: CPI Account
+    #[account(mut)]
+    pub community_token_holding: UncheckedAccount<'info>,
+    /// CHECK: CPI Account
+    #[account(mut)]
+    pub council_token_holding: Option<UncheckedAccount<'info>>,
+    /// CHECK: CPI Account
+    #[account(mut)]
+    pub realm_config: UncheckedAccount<'info>,
+    /// CHECK: CPI Account
+    #[account(mut)]
+    pub governance: UncheckedAccount<'info>,
+    /// CHECK: CPI Account (for seeding)
+    pub governed_account: UncheckedAccount<'info>,
+    /// CHECK: CPI Account
+    #[account(mut)]
+    pub native_treasury: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = native_treasury
+    )]
+    pub dao_token_account: Account<'info, TokenAccount>,
+    /// CHECK: CPI Account
+    #[account(address = REALMS_ID)]
+    pub realm_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>
+}
+
+#[derive(Accounts)]
+pub struct AirdropTokens<'info> {
+    /// The creator, or an operator holding `ROLE_AIRDROP`.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        seeds = [
+            b"tatami-vault"
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = receiver,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    /// CHECK: This account is not read from or write to
+    pub receiver: UncheckedAccount<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        seeds = [
+            b"tatami-role",
+            project.key().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump = role.bump
+    )]
+    pub role: Option<Account<'info, ProjectRole>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct SetAirdropDeadline<'info> {
+    #[account(address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    pub mint: Account<'info, Mint>
+}
+
+#[derive(Accounts)]
+pub struct CloseAirdrop<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        seeds = [
+            b"tatami-vault"
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Only used as the owner of the creator token account
+    #[account(address = project.creator)]
+    pub creator: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = creator
+    )]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = dao_token_account.mint == mint.key(),
+        constraint = dao_token_account.owner == project.treasury
+    )]
+    pub dao_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct BurnVaultTokens<'info> {
+    #[account(
+        constraint = signer.key() == project.creator
+            || (project.dao_init && signer.key() == project.treasury) @ Errors::Unauthorized
+    )]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        seeds = [
+            b"tatami-vault"
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>
+}
+
+#[derive(Accounts)]
+pub struct SweepToTreasury<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        seeds = [
+            b"tatami-vault"
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Native treasury recorded on the project in initialize_dao
+    #[account(address = project.treasury)]
+    pub native_treasury: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = native_treasury
+    )]
+    pub dao_token_account: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>
+}
+
+#[derive(Accounts)]
+pub struct CreateSale<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Sale::INIT_SPACE,
+        seeds = [
+            b"tatami-sale",
+            project.key().as_ref()
+        ],
+        bump
+    )]
+    pub sale: Account<'info, Sale>,
+    #[account(
+        seeds = [
+            b"tatami-vault"
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct Contribute<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(address = sale.project)]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-sale",
+            sale.project.as_ref()
+        ],
+        bump = sale.bump
+    )]
+    pub sale: Account<'info, Sale>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + Contribution::INIT_SPACE,
+        seeds = [
+            b"tatami-contribution",
+            sale.key().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct ClaimSaleTokens<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-sale",
+            project.key().as_ref()
+        ],
+        bump = sale.bump
+    )]
+    pub sale: Account<'info, Sale>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"tatami-contribution",
+            sale.key().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    #[account(
+        seeds = [
+            b"tatami-vault"
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+    )]
+    pub contributor_token_account: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct RefundContribution<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-sale",
+            sale.project.as_ref()
+        ],
+        bump = sale.bump
+    )]
+    pub sale: Account<'info, Sale>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"tatami-contribution",
+            sale.key().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSaleProceeds<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-sale",
+            project.key().as_ref()
+        ],
+        bump = sale.bump
+    )]
+    pub sale: Account<'info, Sale>
+}
+
+#[derive(Accounts)]
+pub struct CloseSale<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"tatami-sale",
+            project.key().as_ref()
+        ],
+        bump = sale.bump
+    )]
+    pub sale: Account<'info, Sale>
+}
+
+#[derive(Accounts)]
+pub struct CreateAuction<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + Auction::INIT_SPACE,
+        seeds = [
+            b"tatami-auction",
+            project.key().as_ref()
+        ],
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+    #[account(
+        seeds = [
+            b"tatami-vault"
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(address = auction.project)]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-auction",
+            auction.project.as_ref()
+        ],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, Auction>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + Bid::INIT_SPACE,
+        seeds = [
+            b"tatami-bid",
+            auction.key().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct ClaimAuctionTokens<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-auction",
+            project.key().as_ref()
+        ],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, Auction>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"tatami-bid",
+            auction.key().as_ref(),
+            signer.key().as_ref()
+        ],
+        bump = bid.bump
+    )]
+    pub bid: Account<'info, Bid>,
+    #[account(
+        seeds = [
+            b"tatami-vault"
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+    )]
+    pub bidder_token_account: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct WithdrawAuctionProceeds<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-auction",
+            project.key().as_ref()
+        ],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, Auction>,
+    /// The DAO treasury when `dao_init` is set, the creator otherwise.
+    #[account(
+        mut,
+        address = project.proceeds_destination() @ Errors::InvalidProceedsDestination
+    )]
+    pub destination: SystemAccount<'info>
+}
+
+#[derive(Accounts)]
+pub struct CloseAuction<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"tatami-auction",
+            project.key().as_ref()
+        ],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, Auction>
+}
+
+#[derive(Accounts)]
+pub struct LockLp<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump,
+        constraint = project.lp_init @ Errors::LpNotInitialized
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + LpLock::INIT_SPACE,
+        seeds = [
+            b"tatami-lp-lock",
+            project.key().as_ref()
+        ],
+        bump
+    )]
+    pub lp_lock: Account<'info, LpLock>,
+    #[account(address = project.lp_mint @ Errors::InvalidLpMint)]
+    pub lp_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = signer
+    )]
+    pub source: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_lock
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct UpdateLpLock<'info> {
+    #[account(address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        seeds = [
+            b"tatami-lp-lock",
+            project.key().as_ref()
+        ],
+        bump = lp_lock.bump
+    )]
+    pub lp_lock: Account<'info, LpLock>
+}
+
+#[derive(Accounts)]
+pub struct BurnLockedLp<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"tatami-lp-lock",
+            project.key().as_ref()
+        ],
+        bump = lp_lock.bump,
+        has_one = lp_mint
+    )]
+    pub lp_lock: Account<'info, LpLock>,
+    #[account(mut)]
+    pub lp_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_lock
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>
+}
+
+#[derive(Accounts)]
+pub struct UnlockLp<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"tatami-lp-lock",
+            project.key().as_ref()
+        ],
+        bump = lp_lock.bump,
+        has_one = lp_mint
+    )]
+    pub lp_lock: Account<'info, LpLock>,
+    pub lp_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_lock
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = lp_mint
+    )]
+    pub destination: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>
+}
+
+#[derive(Accounts)]
+pub struct BurnAuthority<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyCreator<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    pub mint: Account<'info, Mint>,
+    /// CHECK: Address checked, the metadata program checks the signer is listed
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref()
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    pub metadata_program: Program<'info, Metadata>
+}
+
+#[derive(Accounts)]
+pub struct TransferProjectOwnership<'info> {
+    #[account(address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref()
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    pub metadata_program: Program<'info, Metadata>
+}
+
+#[derive(Accounts)]
+pub struct AcceptProjectOwnership<'info> {
+    #[account(address = project.pending_creator @ Errors::Unauthorized)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            project.mint.as_ref()
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    pub metadata_program: Program<'info, Metadata>
+}
+
+#[derive(Accounts)]
+#[instruction(operator: Pubkey)]
+pub struct SetProjectRole<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + ProjectRole::INIT_SPACE,
+        seeds = [
+            b"tatami-role",
+            project.key().as_ref(),
+            operator.as_ref()
+        ],
+        bump
+    )]
+    pub role: Account<'info, ProjectRole>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction(operator: Pubkey)]
+pub struct RemoveProjectRole<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"tatami-role",
+            project.key().as_ref(),
+            operator.as_ref()
+        ],
+        bump = role.bump
+    )]
+    pub role: Account<'info, ProjectRole>
+}
+
+#[derive(Accounts)]
+pub struct SetProjectProfile<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + ProjectProfile::INIT_SPACE,
+        seeds = [
+            b"tatami-profile",
+            project.key().as_ref()
+        ],
+        bump
+    )]
+    pub profile: Account<'info, ProjectProfile>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct CloseProjectProfile<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"tatami-profile",
+            project.key().as_ref()
+        ],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, ProjectProfile>
+}
+
+#[derive(Accounts)]
+#[instruction(page: u16)]
+pub struct RegisterProject<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
-    pub system_program: Program<'info, System>
+    #[account(
+        mut,
+        seeds = [b"tatami-config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-project",
+            project.mint.as_ref()
+        ],
+        bump = project.bump,
+        constraint = !project.registered @ Errors::AlreadyRegistered
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + ProjectIndex::INIT_SPACE,
+        seeds = [
+            b"tatami-index",
+            config.project_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub project_index: Account<'info, ProjectIndex>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + CreatorRegistry::INIT_SPACE,
+        seeds = [
+            b"tatami-creator",
+            project.creator.as_ref(),
+            page.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub creator_registry: Account<'info, CreatorRegistry>,
+    /// Only needed past the first page, to show the one before is full.
+    #[account(
+        seeds = [
+            b"tatami-creator",
+            project.creator.as_ref(),
+            page.saturating_sub(1).to_le_bytes().as_ref()
+        ],
+        bump = previous_page.bump
+    )]
+    pub previous_page: Option<Account<'info, CreatorRegistry>>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct CloseProject<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Account<'info, Project>,
+    #[account(
+        seeds = [
+            b"tatami-vault"
+        ],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    /// CHECK: Only read to make sure the profile was closed first
+    #[account(
+        seeds = [
+            b"tatami-profile",
+            project.key().as_ref()
+        ],
+        bump
+    )]
+    pub profile: UncheckedAccount<'info>,
+    /// CHECK: Only read to make sure no sale is left open
+    #[account(
+        seeds = [
+            b"tatami-sale",
+            project.key().as_ref()
+        ],
+        bump
+    )]
+    pub sale: UncheckedAccount<'info>,
+    /// CHECK: Only read to make sure no auction is left open
+    #[account(
+        seeds = [
+            b"tatami-auction",
+            project.key().as_ref()
+        ],
+        bump
+    )]
+    pub auction: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>
 }
 
 #[derive(Accounts)]
-#[instruction(decimals: u8)]
-pub struct InitProject<'info> {
+pub struct MigrateProject<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    /// CHECK: Decoded by hand in the ix since older layouts don't deserialize as Project
     #[account(
-        init, 
-        payer = signer, 
-        space = 8 + 2 * 32 + 1 * 4 + 2 * 2,
+        mut,
+        owner = crate::ID,
         seeds = [
             b"tatami-project",
             mint.key().as_ref()
         ],
         bump
     )]
-    pub project: Account<'info, Project>,
+    pub project: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    /// CHECK: Decoded by hand in the ix since older layouts don't deserialize as Config
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"tatami-config"],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"tatami-proposal",
+            proposal.id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump
+    )]
+    pub proposal: Option<Account<'info, Proposal>>,
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct InitializeLp<'info> {
+    #[account(mut, address = project.creator)]
+    pub signer: Signer<'info>,
     #[account(
         seeds = [b"tatami-config"],
         bump = config.bump
     )]
-    pub config: Account<'info, Config>,
+    pub config: Box<Account<'info, Config>>,
     #[account(
         mut,
+        seeds = [
+            b"tatami-project",
+            mint.key().as_ref()
+        ],
+        bump = project.bump,
+        has_one = mint
+    )]
+    pub project: Box<Account<'info, Project>>,
+    #[account(
         seeds = [
             b"tatami-vault"
         ],
@@ -189,215 +3321,1146 @@ pub struct InitProject<'info> {
     )]
     pub vault: SystemAccount<'info>,
     #[account(
-        init,
-        payer = signer,
-        mint::decimals = decimals,
-        mint::authority = signer
-    )]
-    pub mint: Account<'info, Mint>,
-    #[account(
-        init,
-        payer = signer,
+        mut,
         associated_token::mint = mint,
-        associated_token::authority = vault,
+        associated_token::authority = vault
     )]
     pub vault_token_account: Account<'info, TokenAccount>,
+    /// Holds the project tokens on their way into the pool.
     #[account(
-        init,
+        init_if_needed,
         payer = signer,
         associated_token::mint = mint,
-        associated_token::authority = team_wallet
+        associated_token::authority = signer
     )]
-    pub team_token_account: Option<Account<'info, TokenAccount>>,
-    pub team_wallet: Option<SystemAccount<'info>>,
-    /// CHECK: This account is initialized in the ix
-    #[account(mut)]
-    pub metadata: UncheckedAccount<'info>,
-    #[account(mut)]
-    pub signer: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    pub creator_token_account: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    /// CHECK: Must be approved in the config, checked in the ix
+    #[account(executable)]
+    pub amm_program: UncheckedAccount<'info>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub metadata_program: Program<'info, Metadata>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Project {
+    pub version: u8,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub dao_init: bool,
+    pub lp_init: bool,
+    pub mint_exist: bool,
+    pub bump: u8,
+    pub recipients: u16,
+    pub recipients_paid: u16,
+    pub state: ProjectState,
+    pub dao_allocation: u64,
+    pub treasury: Pubkey,
+    pub swept: u64,
+    pub airdrop_deadline: i64,
+    pub clawback_policy: ClawbackPolicy,
+    pub airdrop_closed: bool,
+    pub clawed_back: u64,
+    pub burned: u64,
+    pub features: u8,
+    /// Fee charged at launch, in units of `fee_mint`.
+    pub fee_paid: u64,
+    /// Token the fee was paid in, the default key for SOL.
+    pub fee_mint: Pubkey,
+    /// Partner that referred this launch, the default key if none.
+    pub referrer: Pubkey,
+    /// Creator named by `transfer_project_ownership`, until they accept.
+    pub pending_creator: Pubkey,
+    /// Position in the launch registry, only meaningful once `registered`.
+    pub id: u64,
+    /// Set by the permissionless `register_project`, launches start out
+    /// unregistered.
+    pub registered: bool,
+    /// Vault tokens set aside for an open sale, out of reach of airdrops,
+    /// burns and clawbacks.
+    pub reserved_tokens: u64,
+    /// Mirrors the `LpLock` account, so LP state reads off the project alone.
+    pub lp_lock: LpLockStatus,
+    /// LP token of the pool `initialize_lp` seeded, the only one `lock_lp`
+    /// escrows. The default key until then.
+    pub lp_mint: Pubkey,
+    pub pool: Pubkey,
+    /// Zeroed space that new fields are carved out of, so adding a field
+    /// doesn't change the account size. Fields that don't fit grow the
+    /// account, and older accounts only read as `Project` again once
+    /// `migrate_project` has resized them.
+    reserved: [u8; 64]
+}
+
+/// Lifecycle of a launch. A project only ever moves forward:
+/// Created -> Distributed -> DaoLive -> Finalized, where Distributed and
+/// DaoLive may be skipped.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProjectState {
+    Created,
+    Distributed,
+    DaoLive,
+    Finalized
+}
+
+/// Whether the project's LP tokens can still be pulled.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LpLockStatus {
+    Unlocked,
+    /// Escrowed until the lock's `unlock_at`.
+    Locked,
+    /// Escrowed for good.
+    Permanent,
+    /// Burned out of the escrow.
+    Burned
+}
+
+/// Where unclaimed airdrop tokens go once the airdrop deadline has passed.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClawbackPolicy {
+    Creator,
+    Treasury,
+    Burn
+}
+
+impl Project {
+    /// Decodes accounts from before the last size bump too, which are
+    /// shorter: the fields carved out past their end read as zeroes.
+    pub fn try_deserialize_padded(data: &[u8]) -> Result<Self> {
+        let mut padded = data.to_vec();
+        padded.resize(padded.len().max(8 + Project::INIT_SPACE), 0);
+        Project::try_deserialize(&mut &padded[..])
+    }
+
+    /// Legacy launches paid for no features and committed to no DAO
+    /// allocation, so they migrate with neither.
+    pub fn from_legacy(legacy: ProjectV0) -> Self {
+        let state = if !legacy.mint_exist {
+            ProjectState::Finalized
+        } else if legacy.dao_init {
+            ProjectState::DaoLive
+        } else if legacy.recipients_paid > 0 {
+            ProjectState::Distributed
+        } else {
+            ProjectState::Created
+        };
+
+        Self {
+            version: PROJECT_VERSION,
+            creator: legacy.creator,
+            mint: legacy.mint,
+            dao_init: legacy.dao_init,
+            lp_init: legacy.lp_init,
+            mint_exist: legacy.mint_exist,
+            bump: legacy.bump,
+            recipients: legacy.recipients,
+            recipients_paid: legacy.recipients_paid,
+            state,
+            dao_allocation: 0,
+            treasury: Pubkey::default(),
+            swept: 0,
+            airdrop_deadline: 0,
+            clawback_policy: ClawbackPolicy::Creator,
+            airdrop_closed: false,
+            clawed_back: 0,
+            burned: 0,
+            features: 0,
+            fee_paid: 0,
+            fee_mint: Pubkey::default(),
+            referrer: Pubkey::default(),
+            pending_creator: Pubkey::default(),
+            id: 0,
+            registered: false,
+            reserved_tokens: 0,
+            lp_lock: LpLockStatus::Unlocked,
+            lp_mint: Pubkey::default(),
+            pool: Pubkey::default(),
+            reserved: [0; 64]
+        }
+    }
+
+    /// Where sale proceeds go: the DAO treasury once it exists, else the creator.
+    pub fn proceeds_destination(&self) -> Pubkey {
+        if self.dao_init {
+            self.treasury
+        } else {
+            self.creator
+        }
+    }
+
+    /// Whether the airdrop can no longer pay anyone: closed, past its
+    /// deadline, or every recipient paid.
+    pub fn airdrop_ended(&self, now: i64) -> bool {
+        self.airdrop_closed
+            || (self.airdrop_deadline != 0 && now > self.airdrop_deadline)
+            || self.recipients_paid >= self.recipients
+    }
+
+    /// Part of the vault balance not reserved for a sale.
+    pub fn available_tokens(&self, vault_balance: u64) -> u64 {
+        vault_balance.saturating_sub(self.reserved_tokens)
+    }
+
+    /// Fails once the project is finalized: airdrops, raises and liquidity
+    /// all have to be opened while the launch is still running.
+    pub fn require_open(&self) -> Result<()> {
+        require!(self.state != ProjectState::Finalized, Errors::ProjectFinalized);
+        Ok(())
+    }
+
+    pub fn transition(&mut self, next: ProjectState) -> Result<()> {
+        use ProjectState::*;
+
+        let allowed = matches!(
+            (self.state, next),
+            (Created, Distributed)
+                | (Created | Distributed, DaoLive)
+                | (Created | Distributed | DaoLive, Finalized)
+        );
+
+        require!(allowed, Errors::InvalidStateTransition);
+        self.state = next;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub version: u8,
+    pub fee: u64,
+    pub creator: Pubkey,
+    pub bump: u8,
+    pub dao_fee: u64,
+    pub lp_fee: u64,
+    pub vesting_fee: u64,
+    pub holder_discount_mint: Pubkey,
+    pub holder_discount_min_balance: u64,
+    pub holder_discount_bps: u16,
+    /// Stops every pausable instruction.
+    pub paused: bool,
+    /// `PAUSE_*` bits for instructions paused individually.
+    pub pause_flags: u8,
+    /// Only creators holding a `CreatorPermit` may launch.
+    pub allowlist_enabled: bool,
+    /// Key trusted to grant permits besides the admin, the default key if none.
+    pub verifier: Pubkey,
+    /// M-of-N admins replacing `creator` once `threshold` is non-zero. Only
+    /// the first `admin_count` entries are set.
+    pub admins: [Pubkey; MAX_ADMINS],
+    pub admin_count: u8,
+    pub threshold: u8,
+    /// Bumped whenever the admin set changes, voiding open proposals.
+    pub admin_epoch: u32,
+    pub proposal_count: u64,
+    /// Projects in the registry, also the id the next one gets.
+    pub project_count: u64,
+    /// AMM programs `initialize_lp` may call. Only the first `amm_count`
+    /// entries are set.
+    pub amm_programs: [ApprovedAmm; MAX_AMM_PROGRAMS],
+    pub amm_count: u8,
+    /// Zeroed space that new fields are carved out of, so adding a field
+    /// doesn't change the account size.
+    reserved: [u8; 96]
+}
+
+impl Config {
+    pub fn from_legacy(legacy: ConfigV0) -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            fee: legacy.fee,
+            creator: legacy.creator,
+            bump: legacy.bump,
+            dao_fee: 0,
+            lp_fee: 0,
+            vesting_fee: 0,
+            holder_discount_mint: Pubkey::default(),
+            holder_discount_min_balance: 0,
+            holder_discount_bps: 0,
+            paused: false,
+            pause_flags: 0,
+            allowlist_enabled: false,
+            verifier: Pubkey::default(),
+            admins: [Pubkey::default(); MAX_ADMINS],
+            admin_count: 0,
+            threshold: 0,
+            admin_epoch: 0,
+            proposal_count: 0,
+            project_count: 0,
+            amm_programs: [ApprovedAmm::default(); MAX_AMM_PROGRAMS],
+            amm_count: 0,
+            reserved: [0; 96]
+        }
+    }
+
+    /// Hands out the next registry id.
+    pub fn next_project_id(&mut self) -> u64 {
+        let id = self.project_count;
+        self.project_count += 1;
+        id
+    }
+
+    pub fn is_verifier(&self, signer: &Pubkey) -> bool {
+        self.verifier != Pubkey::default() && *signer == self.verifier
+    }
+
+    pub fn admin_index(&self, key: &Pubkey) -> Option<usize> {
+        self.admins[..self.admin_count as usize].iter().position(|admin| admin == key)
+    }
+
+    pub fn is_admin(&self, key: &Pubkey) -> bool {
+        self.admin_index(key).is_some()
+    }
+
+    /// How to drive `program` if the admin approved it.
+    pub fn approved_amm(&self, program: &Pubkey) -> Option<AmmKind> {
+        self.amm_programs[..self.amm_count as usize]
+            .iter()
+            .find(|amm| amm.program == *program)
+            .map(|amm| amm.kind)
+    }
+
+    /// Checks that `signer` may run `action`. Without an admin set that is
+    /// the config creator; otherwise any admin executing a proposal for
+    /// exactly this action that reached the threshold, which is then spent.
+    pub fn authorize(&self, signer: &Pubkey, proposal: Option<&mut Proposal>, action: AdminAction) -> Result<()> {
+        if self.threshold == 0 {
+            require_keys_eq!(*signer, self.creator, Errors::Unauthorized);
+            return Ok(());
+        }
+
+        require!(self.is_admin(signer), Errors::Unauthorized);
+
+        let proposal = proposal.ok_or(Errors::ProposalRequired)?;
+
+        require!(!proposal.executed, Errors::ProposalExecuted);
+        require_eq!(proposal.admin_epoch, self.admin_epoch, Errors::ProposalStale);
+        require_gte!(proposal.approvals.count_ones(), self.threshold as u32, Errors::ProposalNotApproved);
+        require!(proposal.action == action, Errors::ProposalMismatch);
+
+        proposal.executed = true;
+        Ok(())
+    }
+
+    /// Fails if the program, or the instruction behind `flag`, is paused.
+    pub fn require_active(&self, flag: u8) -> Result<()> {
+        require!(!self.paused && self.pause_flags & flag == 0, Errors::Paused);
+        Ok(())
+    }
+
+    /// Launch fee before discounts: the base fee plus every requested feature.
+    pub fn gross_fee(&self, features: u8) -> Result<u64> {
+        let extras = [
+            (FEATURE_DAO, self.dao_fee),
+            (FEATURE_LP, self.lp_fee),
+            (FEATURE_VESTING, self.vesting_fee)
+        ];
+
+        extras.iter()
+            .filter(|(feature, _)| features & feature != 0)
+            .try_fold(self.fee, |total, (_, fee)| total.checked_add(*fee))
+            .ok_or(error!(Errors::FeeOverflow))
+    }
+}
+
+/// Per-creator fee terms granted by the admin.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeOverride {
+    pub creator: Pubkey,
+    pub discount_bps: u16,
+    pub waived: bool,
+    pub bump: u8
+}
+
+/// Arguments of `init_project`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InitProjectArgs {
+    pub decimals: u8,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub recipients: u16,
+    /// Team allocation and airdrop supply, in base units.
+    pub supply: [u64; 2],
+    pub dao_allocation: u64,
+    /// Unix timestamp after which the airdrop can be closed, 0 for none.
+    pub airdrop_deadline: i64,
+    pub clawback_policy: ClawbackPolicy,
+    /// `FEATURE_*` flags to pay for.
+    pub features: u8,
+    /// Metadata creators, shares summing to 100. Empty lists the signer alone.
+    pub creators: Vec<CreatorShare>,
+    pub collection: Option<Pubkey>
+}
+
+/// A creator to list in the token metadata, with their share out of 100.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CreatorShare {
+    pub address: Pubkey,
+    pub share: u8
+}
+
+/// Checks creators are unique, at most `MAX_CREATORS`, with shares summing
+/// to 100.
+pub fn validate_creators(creators: &[CreatorShare]) -> Result<()> {
+    require_gte!(MAX_CREATORS, creators.len(), Errors::InvalidCreators);
+
+    for (i, creator) in creators.iter().enumerate() {
+        require!(
+            !creators[..i].iter().any(|other| other.address == creator.address),
+            Errors::InvalidCreators
+        );
+    }
+
+    let total: u16 = creators.iter().map(|creator| creator.share as u16).sum();
+    require_eq!(total, 100, Errors::InvalidCreators);
+    Ok(())
+}
+
+/// Roles delegated by a project's creator to `operator`.
+#[account]
+#[derive(InitSpace)]
+pub struct ProjectRole {
+    pub project: Pubkey,
+    pub operator: Pubkey,
+    /// `ROLE_*` bits.
+    pub roles: u8,
+    /// Creator that granted the roles. They lapse once the project changes
+    /// hands, until the new creator grants them again.
+    pub granted_by: Pubkey,
+    pub bump: u8
+}
+
+/// Presale of vault tokens for SOL. Contributions are held by this account
+/// until they are refunded or withdrawn by the creator.
+#[account]
+#[derive(InitSpace)]
+pub struct Sale {
+    pub project: Pubkey,
+    pub start: i64,
+    pub end: i64,
+    /// Lamports to raise for the sale to succeed.
+    pub soft_cap: u64,
+    /// Lamports after which contributions stop and the sale ends early.
+    pub hard_cap: u64,
+    pub min_contribution: u64,
+    /// Most a wallet may contribute in total, 0 for no limit below the hard cap.
+    pub max_contribution: u64,
+    /// Root of the allowlist built by `allowlist_leaf`, zeroed for an open sale.
+    pub merkle_root: [u8; 32],
+    /// Tokens split pro rata between contributors.
+    pub token_amount: u64,
+    pub total_raised: u64,
+    pub contributors: u32,
+    /// Contributions claimed or refunded so far.
+    pub settled: u32,
+    pub tokens_claimed: u64,
+    pub proceeds_withdrawn: bool,
+    pub bump: u8
+}
+
+impl Sale {
+    pub fn has_ended(&self, now: i64) -> bool {
+        now >= self.end || self.total_raised >= self.hard_cap
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.total_raised >= self.soft_cap
+    }
+
+    /// Tokens owed for `contributed` lamports.
+    pub fn allocation(&self, contributed: u64) -> u64 {
+        (self.token_amount as u128 * contributed as u128 / self.total_raised as u128) as u64
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Contribution {
+    pub sale: Pubkey,
+    pub contributor: Pubkey,
+    /// Lamports contributed in total.
+    pub amount: u64,
+    pub bump: u8
+}
+
+/// Arguments of `create_sale`, amounts in lamports except `token_amount`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SaleArgs {
+    pub start: i64,
+    pub end: i64,
+    pub soft_cap: u64,
+    pub hard_cap: u64,
+    pub min_contribution: u64,
+    pub max_contribution: u64,
+    pub merkle_root: [u8; 32],
+    pub token_amount: u64
+}
+
+impl SaleArgs {
+    pub fn validate(&self, now: i64) -> Result<()> {
+        let valid = self.end > self.start
+            && self.end > now
+            && self.hard_cap > 0
+            && self.soft_cap <= self.hard_cap
+            && self.min_contribution <= self.hard_cap
+            && (self.max_contribution == 0 || self.min_contribution <= self.max_contribution)
+            && self.token_amount > 0;
+
+        require!(valid, Errors::InvalidSaleConfig);
+        Ok(())
+    }
+}
+
+/// Allowlist leaf for `wallet`.
+pub fn allowlist_leaf(wallet: &Pubkey) -> [u8; 32] {
+    keccak::hashv(&[wallet.as_ref()]).0
+}
+
+/// Checks `wallet` against a merkle root whose pairs are hashed in sorted order.
+pub fn verify_allowlist_proof(proof: &[[u8; 32]], root: &[u8; 32], wallet: &Pubkey) -> bool {
+    let node = proof.iter().fold(allowlist_leaf(wallet), |node, sibling| {
+        if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).0
+        } else {
+            keccak::hashv(&[sibling, &node]).0
+        }
+    });
+
+    node == *root
+}
+
+/// Escrow for a project's LP tokens. The escrow is the lock's associated
+/// token account for `lp_mint`.
+#[account]
+#[derive(InitSpace)]
+pub struct LpLock {
+    pub project: Pubkey,
+    pub lp_mint: Pubkey,
+    /// LP tokens locked in total.
+    pub amount: u64,
+    pub unlock_at: i64,
+    pub bump: u8
+}
+
+/// Descending-price auction of vault tokens. Every bidder pays the clearing
+/// price: the price at which the last token sold, or the floor if some are
+/// left at `end`. Deposits are held by this account until claims.
+#[account]
+#[derive(InitSpace)]
+pub struct Auction {
+    pub project: Pubkey,
+    pub start: i64,
+    pub end: i64,
+    /// Lamports per whole token at `start`.
+    pub start_price: u64,
+    /// Lamports per whole token reached at `end`.
+    pub floor_price: u64,
+    /// Seconds between price drops.
+    pub decay_interval: u64,
+    pub token_amount: u64,
+    pub decimals: u8,
+    pub tokens_committed: u64,
+    pub total_deposited: u64,
+    /// Price of the bid that sold out the auction, 0 until then.
+    pub clearing_price: u64,
+    pub bidders: u32,
+    /// Bids claimed so far.
+    pub settled: u32,
+    pub tokens_claimed: u64,
+    pub proceeds_withdrawn: bool,
+    pub bump: u8
+}
+
+impl Auction {
+    pub fn has_ended(&self, now: i64) -> bool {
+        now >= self.end || self.tokens_committed == self.token_amount
+    }
+
+    /// Price in effect at `now`, dropping linearly in steps of `decay_interval`
+    /// and landing on the floor at `end`.
+    pub fn price_at(&self, now: i64) -> u64 {
+        if now >= self.end {
+            return self.floor_price;
+        }
+
+        let duration = (self.end - self.start) as u128;
+        let elapsed = (now.max(self.start) - self.start) as u64;
+        let decayed = (elapsed / self.decay_interval * self.decay_interval) as u128;
+        let drop = (self.start_price - self.floor_price) as u128 * decayed / duration;
+
+        self.start_price - drop as u64
+    }
+
+    pub fn final_price(&self) -> u64 {
+        if self.tokens_committed == self.token_amount {
+            self.clearing_price
+        } else {
+            self.floor_price
+        }
+    }
+
+    /// Lamports for `quantity` base units at `price`, rounded up so the
+    /// escrow always covers refunds.
+    pub fn cost(&self, quantity: u64, price: u64) -> Result<u64> {
+        let unit = 10u128.checked_pow(self.decimals as u32).ok_or(Errors::MathOverflow)?;
+        let lamports = (quantity as u128 * price as u128).div_ceil(unit);
+
+        u64::try_from(lamports).map_err(|_| error!(Errors::MathOverflow))
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Bid {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    /// Base units won.
+    pub quantity: u64,
+    /// Lamports paid at the prices bid, above the final cost.
+    pub deposited: u64,
+    pub bump: u8
+}
+
+/// Arguments of `create_auction`, prices in lamports per whole token.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AuctionArgs {
+    pub start: i64,
+    pub end: i64,
+    pub start_price: u64,
+    pub floor_price: u64,
+    pub decay_interval: u64,
+    pub token_amount: u64
+}
+
+impl AuctionArgs {
+    pub fn validate(&self, now: i64) -> Result<()> {
+        let valid = self.end > self.start
+            && self.end > now
+            && self.start_price >= self.floor_price
+            && self.floor_price > 0
+            && self.decay_interval > 0
+            && self.decay_interval <= (self.end - self.start) as u64
+            && self.token_amount > 0;
+
+        require!(valid, Errors::InvalidAuctionConfig);
+        Ok(())
+    }
+}
+
+/// Public details frontends render on a project page. The description lives
+/// off chain, `description_hash` is its SHA-256 so it can be checked.
+#[account]
+#[derive(InitSpace)]
+pub struct ProjectProfile {
+    pub project: Pubkey,
+    #[max_len(MAX_PROFILE_LINK_LEN)]
+    pub website: String,
+    #[max_len(MAX_PROFILE_SOCIALS, MAX_PROFILE_LINK_LEN)]
+    pub socials: Vec<String>,
+    pub description_hash: [u8; 32],
+    pub category: ProjectCategory,
+    #[max_len(MAX_PROFILE_TAGS, MAX_PROFILE_TAG_LEN)]
+    pub tags: Vec<String>,
+    pub updated_at: i64,
+    pub bump: u8
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProjectCategory {
+    Other,
+    Meme,
+    Defi,
+    Gaming,
+    Nft,
+    Infrastructure,
+    Social,
+    Dao
+}
+
+/// Arguments of `set_project_profile`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProfileData {
+    pub website: String,
+    pub socials: Vec<String>,
+    pub description_hash: [u8; 32],
+    pub category: ProjectCategory,
+    pub tags: Vec<String>
+}
+
+impl ProfileData {
+    pub fn validate(&self) -> Result<()> {
+        require_gte!(MAX_PROFILE_SOCIALS, self.socials.len(), Errors::TooManyProfileEntries);
+        require_gte!(MAX_PROFILE_TAGS, self.tags.len(), Errors::TooManyProfileEntries);
+
+        let links_fit = std::iter::once(&self.website)
+            .chain(&self.socials)
+            .all(|link| link.len() <= MAX_PROFILE_LINK_LEN);
+        let tags_fit = self.tags.iter().all(|tag| !tag.is_empty() && tag.len() <= MAX_PROFILE_TAG_LEN);
+
+        require!(links_fit && tags_fit, Errors::InvalidProfileField);
+        Ok(())
+    }
 }
 
-#[derive(Accounts)]
-pub struct InitializeDao<'info> {
-    #[account(mut, address = project.creator)]
-    pub signer: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [
-            b"tatami-project",
-            mint.key().as_ref()
-        ],
-        bump = project.bump,
-        has_one = mint
-    )]
-    pub project: Account<'info, Project>,
-    pub mint: Account<'info, Mint>,
-    #[account(
-        init,
-        payer = signer,
-        mint::decimals = 1,
-        mint::authority = signer
-    )]
-    pub council_mint: Option<Account<'info, Mint>>,
-    /// CHECK: CPI Account
-    #[account(mut)]
-    pub realm_account: UncheckedAccount<'info>,
-    /// CHECK: CPI Account
-    #[account(mut)]
-    pub community_token_holding: UncheckedAccount<'info>,
-    /// CHECK: CPI Account
-    #[account(mut)]
-    pub council_token_holding: Option<UncheckedAccount<'info>>,
-    /// CHECK: CPI Account
-    #[account(mut)]
-    pub realm_config: UncheckedAccount<'info>,
-    /// CHECK: CPI Account
-    #[account(mut)]
-    pub governance: UncheckedAccount<'info>,
-    /// CHECK: CPI Account (for seeding)
-    pub governed_account: UncheckedAccount<'info>,
-    /// CHECK: CPI Account
-    #[account(mut)]
-    pub native_treasury: UncheckedAccount<'info>,
-    #[account(
-        init,
-        payer = signer,
-        associated_token::mint = mint,
-        associated_token::authority = native_treasury
-    )]
-    pub dao_token_account: Account<'info, TokenAccount>,
-    /// CHECK: CPI Account
-    #[account(address = REALMS_ID)]
-    pub realm_program: UncheckedAccount<'info>,
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>
+/// Registry entry pointing from a project's id to the project, so launches
+/// can be paged through in order.
+#[account]
+#[derive(InitSpace)]
+pub struct ProjectIndex {
+    pub id: u64,
+    pub project: Pubkey,
+    pub mint: Pubkey,
+    pub bump: u8
 }
 
-#[derive(Accounts)]
-pub struct AirdropTokens<'info> {
-    #[account(mut, address = project.creator)]
-    pub signer: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [
-            b"tatami-project",
-            mint.key().as_ref()
-        ],
-        bump = project.bump,
-        has_one = mint
-    )]
-    pub project: Account<'info, Project>,
-    #[account(
-        seeds = [
-            b"tatami-vault"
-        ],
-        bump
-    )]
-    pub vault: SystemAccount<'info>,
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = vault,
-    )]
-    pub vault_token_account: Account<'info, TokenAccount>,
-    #[account(
-        init_if_needed,
-        payer = signer,
-        associated_token::mint = mint,
-        associated_token::authority = receiver,
-    )]
-    pub recipient_token_account: Account<'info, TokenAccount>,
-    /// CHECK: This account is not read from or write to
-    pub receiver: UncheckedAccount<'info>,
-    pub mint: Account<'info, Mint>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>
+/// One page of the ids registered for `creator`, oldest first; the list
+/// carries on at the next page once this one is full. Handovers don't move
+/// projects between lists.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorRegistry {
+    pub creator: Pubkey,
+    pub bump: u8,
+    #[max_len(MAX_CREATOR_PROJECTS)]
+    pub projects: Vec<u64>
 }
 
-#[derive(Accounts)]
-pub struct BurnAuthority<'info> {
-    #[account(mut, address = project.creator)]
-    pub signer: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [
-            b"tatami-project",
-            mint.key().as_ref()
-        ],
-        bump = project.bump,
-        has_one = mint
-    )]
-    pub project: Account<'info, Project>,
-    pub mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
+impl CreatorRegistry {
+    pub fn append(&mut self, creator: Pubkey, bump: u8, id: u64) -> Result<()> {
+        require_gt!(MAX_CREATOR_PROJECTS, self.projects.len(), Errors::CreatorRegistryFull);
+
+        self.creator = creator;
+        self.bump = bump;
+        self.projects.push(id);
+        Ok(())
+    }
 }
 
-#[derive(Accounts)]
-pub struct InitializeLp {}
+/// An admin action waiting for approvals from the config's admin set.
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    /// Bit `i` is set once `config.admins[i]` approved.
+    pub approvals: u8,
+    pub admin_epoch: u32,
+    pub executed: bool,
+    pub bump: u8
+}
 
-#[account] 
-pub struct Project {
-    creator: Pubkey,
-    mint: Pubkey,
-    dao_init: bool,
-    lp_init: bool,
-    mint_exist: bool,
-    bump: u8,
-    recipients: u16,
-    recipients_paid: u16
+/// Admin instructions with the arguments and accounts they must be run with
+/// to execute a proposal.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Debug, PartialEq, Eq)]
+pub enum AdminAction {
+    UpdateConfig { fee: u64 },
+    SetFeeSchedule { schedule: FeeSchedule },
+    SetPause { paused: bool, pause_flags: u8 },
+    SetAllowlist { enabled: bool, verifier: Pubkey },
+    SetAdmins {
+        #[max_len(MAX_ADMINS)]
+        admins: Vec<Pubkey>,
+        threshold: u8
+    },
+    SetFeeOverride { creator: Pubkey, discount_bps: u16, waived: bool },
+    RemoveFeeOverride { creator: Pubkey },
+    SetReferral { referrer: Pubkey, share_bps: u16 },
+    RemoveReferral { referrer: Pubkey },
+    GrantCreatorPermit { creator: Pubkey, expires_at: i64 },
+    RevokeCreatorPermit { creator: Pubkey },
+    AddFeeMint { mint: Pubkey, price: u64 },
+    UpdateFeeMint { mint: Pubkey, price: u64, enabled: bool },
+    WithdrawFees { destination: Pubkey, amount: u64 },
+    WithdrawTokenFees { mint: Pubkey, destination: Pubkey, amount: u64 },
+    ApproveAmm { program: Pubkey, kind: AmmKind },
+    RevokeAmm { program: Pubkey },
+    MigrateConfig
 }
 
+/// Allows `creator` to launch while the allowlist is on.
 #[account]
-pub struct Config {
-    fee: u64,
-    creator: Pubkey,
-    bump: u8
+#[derive(InitSpace)]
+pub struct CreatorPermit {
+    pub creator: Pubkey,
+    /// Unix timestamp the permit stops working at, 0 if it never expires.
+    pub expires_at: i64,
+    /// Admin or verifier that granted the permit.
+    pub issuer: Pubkey,
+    pub revoked: bool,
+    /// Bumped on every revoke, so attestations signed before it can't be
+    /// claimed again.
+    pub nonce: u64,
+    pub bump: u8
+}
+
+impl CreatorPermit {
+    pub fn is_valid(&self, now: i64) -> bool {
+        self.expires_at == 0 || now < self.expires_at
+    }
 }
 
-impl<'info> InitProject<'info> {
-    pub fn create_metadata_ctx(&self) -> CpiContext<'_, '_, '_, 'info, CreateMetadataAccountsV3<'info>> {
-        let cpi_program = self.metadata_program.to_account_info();
-        let cpi_accounts = CreateMetadataAccountsV3 {
-            metadata: self.metadata.to_account_info(),
-            mint: self.mint.to_account_info(),
-            mint_authority: self.signer.to_account_info(),
-            payer: self.signer.to_account_info(),
-            update_authority: self.signer.to_account_info(),
-            system_program: self.system_program.to_account_info(),
-            rent: self.rent.to_account_info()
+/// Message a verifier signs to approve `creator` off-chain, `nonce` being
+/// the creator's permit nonce.
+pub fn permit_message(creator: &Pubkey, nonce: u64, expires_at: i64) -> Vec<u8> {
+    [b"tatami-permit".as_ref(), creator.as_ref(), &nonce.to_le_bytes(), &expires_at.to_le_bytes()].concat()
+}
+
+/// Checks that the instruction before the current one is an ed25519
+/// program check of a single signature by `signer` over `message`.
+pub fn verify_attestation(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    const HEADER_LEN: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+    let index = load_current_index_checked(instructions)?;
+    require_gt!(index, 0, Errors::InvalidAttestation);
+
+    let ix = load_instruction_at_checked(index as usize - 1, instructions)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, Errors::InvalidAttestation);
+
+    let data = &ix.data;
+    require!(data.len() >= HEADER_LEN + OFFSETS_LEN && data[0] == 1, Errors::InvalidAttestation);
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = HEADER_LEN;
+
+    // signature, public key and message must all live in the ed25519 instruction itself
+    require!(
+        read_u16(offsets + 2) == CURRENT_INSTRUCTION
+            && read_u16(offsets + 6) == CURRENT_INSTRUCTION
+            && read_u16(offsets + 12) == CURRENT_INSTRUCTION,
+        Errors::InvalidAttestation
+    );
+
+    let key_start = read_u16(offsets + 4) as usize;
+    let message_start = read_u16(offsets + 8) as usize;
+    let message_len = read_u16(offsets + 10) as usize;
+
+    let key = data.get(key_start..key_start + 32).ok_or(Errors::InvalidAttestation)?;
+    let signed = data.get(message_start..message_start + message_len).ok_or(Errors::InvalidAttestation)?;
+
+    require!(key == signer.as_ref() && signed == message, Errors::InvalidAttestation);
+    Ok(())
+}
+
+/// A partner that earns a share of the launch fees of projects it refers.
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub referrer: Pubkey,
+    pub share_bps: u16,
+    pub projects_referred: u64,
+    /// Running total of SOL fee shares; token shares are totalled per mint
+    /// in `ReferralEarnings`.
+    pub lamports_earned: u64,
+    pub bump: u8
+}
+
+/// Running total of a referrer's fee shares paid in one fee mint.
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralEarnings {
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    /// In `mint` base units.
+    pub amount: u64,
+    pub bump: u8
+}
+
+/// A token accepted for launch fees, with its own vault owned by the config.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeMint {
+    pub mint: Pubkey,
+    /// Token base units charged per SOL of fee.
+    pub price: u64,
+    pub enabled: bool,
+    pub bump: u8
+}
+
+impl FeeMint {
+    /// Converts a fee in lamports into this token's base units.
+    pub fn token_fee(&self, lamports: u64) -> Result<u64> {
+        let amount = lamports as u128 * self.price as u128 / LAMPORTS_PER_SOL as u128;
+        u64::try_from(amount).map_err(|_| error!(Errors::FeeOverflow))
+    }
+}
+
+/// Fees for optional features and the token-holder discount.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Debug, PartialEq, Eq)]
+pub struct FeeSchedule {
+    pub dao_fee: u64,
+    pub lp_fee: u64,
+    pub vesting_fee: u64,
+    pub holder_discount_mint: Pubkey,
+    pub holder_discount_min_balance: u64,
+    pub holder_discount_bps: u16
+}
+
+/// Applies a discount in basis points, rounding the fee down.
+pub fn apply_discount(fee: u64, discount_bps: u16) -> u64 {
+    let discount = fee as u128 * discount_bps.min(MAX_BASIS_POINTS) as u128 / MAX_BASIS_POINTS as u128;
+    fee - discount as u64
+}
+
+/// Size of a Project account created before versioning was introduced.
+pub const LEGACY_PROJECT_LEN: usize = 8 + ProjectV0::INIT_SPACE;
+
+/// Size of a Config account created before versioning was introduced.
+pub const LEGACY_CONFIG_LEN: usize = 8 + ConfigV0::INIT_SPACE;
+
+/// Project layout before versioning, kept only to migrate old accounts.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct ProjectV0 {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub dao_init: bool,
+    pub lp_init: bool,
+    pub mint_exist: bool,
+    pub bump: u8,
+    pub recipients: u16,
+    pub recipients_paid: u16
+}
+
+/// Config layout before versioning, kept only to migrate old accounts.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct ConfigV0 {
+    pub fee: u64,
+    pub creator: Pubkey,
+    pub bump: u8
+}
+
+/// Moves lamports out of an account owned by this program.
+pub fn withdraw_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? -= amount;
+    **to.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
+
+/// Grows `account` to `new_len`, topping up its lamports from `payer` so it
+/// stays rent exempt.
+pub fn realloc_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(new_len);
+    let current = account.lamports();
+
+    if required > current {
+        let cpi_accounts = Transfer {
+            from: payer.clone(),
+            to: account.clone()
+        };
+
+        transfer(CpiContext::new(system_program.clone(), cpi_accounts), required - current)?;
+    }
+
+    account.realloc(new_len, true)?;
+    Ok(())
+}
+
+impl<'info> InitProject<'info> {
+    /// Enforces the creator allowlist when the config has it on.
+    pub fn check_permit(&self) -> Result<()> {
+        if !self.config.allowlist_enabled {
+            return Ok(());
+        }
+
+        let permit = self.creator_permit.as_ref().ok_or(Errors::CreatorNotAllowed)?;
+        require!(!permit.revoked, Errors::CreatorNotAllowed);
+        require!(permit.is_valid(Clock::get()?.unix_timestamp), Errors::PermitExpired);
+        Ok(())
+    }
+
+    pub fn create_metadata_ctx(&self) -> CpiContext<'_, '_, '_, 'info, CreateMetadataAccountsV3<'info>> {
+        let cpi_program = self.metadata_program.to_account_info();
+        let cpi_accounts = CreateMetadataAccountsV3 {
+            metadata: self.metadata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            mint_authority: self.signer.to_account_info(),
+            payer: self.signer.to_account_info(),
+            update_authority: self.signer.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            rent: self.rent.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Fee for this launch after the best discount the signer qualifies for.
+    /// Discounts don't stack; a waiver makes the launch free.
+    pub fn launch_fee(&self, features: u8) -> Result<u64> {
+        let gross = self.config.gross_fee(features)?;
+        let mut discount_bps = 0;
+
+        if let Some(fee_override) = self.fee_override.as_ref() {
+            if fee_override.waived {
+                return Ok(0);
+            }
+            discount_bps = fee_override.discount_bps;
+        }
+
+        if let Some(token_account) = self.discount_token_account.as_ref() {
+            require_gte!(
+                token_account.amount,
+                self.config.holder_discount_min_balance,
+                Errors::DiscountNotEligible
+            );
+            discount_bps = discount_bps.max(self.config.holder_discount_bps);
+        }
+
+        Ok(apply_discount(gross, discount_bps))
+    }
+
+    pub fn transfer_sol_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            to: self.vault.to_account_info(),
+            from: self.signer.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Referrer and the lamports of `fee` owed to them, zero without a referral.
+    pub fn referral_share(&self, fee: u64) -> Result<(Pubkey, u64)> {
+        let Some(referral) = self.referral.as_ref() else {
+            return Ok((Pubkey::default(), 0));
+        };
+
+        require_keys_neq!(referral.referrer, self.signer.key(), Errors::InvalidReferrer);
+
+        let share = fee - apply_discount(fee, referral.share_bps);
+        Ok((referral.referrer, share))
+    }
+
+    /// Sends the referrer's `share` of a SOL fee straight to their wallet.
+    pub fn pay_referrer(&self, share: u64) -> Result<()> {
+        let referral = self.referral.as_ref().ok_or(Errors::MissingReferrerAccount)?;
+        let referrer = self.referrer.as_ref().ok_or(Errors::MissingReferrerAccount)?;
+
+        require_keys_eq!(referrer.key(), referral.referrer, Errors::InvalidReferrer);
+
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            to: referrer.to_account_info(),
+            from: self.signer.to_account_info()
+        };
+
+        transfer(CpiContext::new(cpi_program, cpi_accounts), share)
+    }
+
+    /// Charges `fee` (in lamports) in the fee mint instead, moving it into the
+    /// config's vault for that mint and the referrer's `share` to their token
+    /// account. Returns the amount, mint and referral share charged.
+    pub fn pay_fee_in_tokens(&self, fee: u64, share: u64) -> Result<(u64, Pubkey, u64)> {
+        let fee_mint = self.fee_mint.as_ref().ok_or(Errors::MissingFeeAccount)?;
+        let mint = self.fee_token_mint.as_ref().ok_or(Errors::MissingFeeAccount)?;
+        let from = self.payer_token_account.as_ref().ok_or(Errors::MissingFeeAccount)?;
+        let to = self.fee_vault.as_ref().ok_or(Errors::MissingFeeAccount)?;
+
+        require!(fee_mint.enabled, Errors::FeeMintDisabled);
+        require_keys_eq!(mint.key(), fee_mint.mint, Errors::InvalidFeeAccount);
+        require_keys_eq!(
+            to.key(),
+            get_associated_token_address(&self.config.key(), &fee_mint.mint),
+            Errors::InvalidFeeAccount
+        );
+
+        let amount = fee_mint.token_fee(fee)?;
+        let token_share = fee_mint.token_fee(share)?;
+
+        if token_share > 0 {
+            let referral = self.referral.as_ref().ok_or(Errors::MissingReferrerAccount)?;
+            let referrer = self.referrer_token_account.as_ref().ok_or(Errors::MissingReferrerAccount)?;
+            let earnings = self.referral_earnings.as_ref().ok_or(Errors::MissingReferrerAccount)?;
+
+            require_keys_eq!(referrer.owner, referral.referrer, Errors::InvalidReferrer);
+            require_keys_eq!(referrer.mint, fee_mint.mint, Errors::InvalidReferrer);
+            require_keys_eq!(earnings.referrer, referral.referrer, Errors::InvalidReferrer);
+            require_keys_eq!(earnings.mint, fee_mint.mint, Errors::InvalidReferrer);
+
+            self.transfer_fee_tokens(from, referrer, mint, token_share)?;
+        }
+
+        if amount > token_share {
+            self.transfer_fee_tokens(from, to, mint, amount - token_share)?;
+        }
+
+        Ok((amount, fee_mint.mint, token_share))
+    }
+
+    fn transfer_fee_tokens(
+        &self,
+        from: &Account<'info, TokenAccount>,
+        to: &Account<'info, TokenAccount>,
+        mint: &Account<'info, Mint>,
+        amount: u64
+    ) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: self.signer.to_account_info()
+        };
+
+        transfer_checked(CpiContext::new(cpi_program, cpi_accounts), amount, mint.decimals)
+    }
+
+    pub fn mint_supply_ctx(&self, is_team: bool) -> CpiContext<'_, '_, '_, 'info, MintTo<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = MintTo {
+            mint: self.mint.to_account_info(),
+            to: if is_team {
+                self.team_token_account.as_ref().unwrap().to_account_info()
+            } else {
+                self.vault_token_account.to_account_info()
+            },
+            authority: self.signer.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    pub fn set_auth_ctx(&self) -> CpiContext<'_, '_, '_, 'info, SetAuthority<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = SetAuthority {
+            current_authority: self.signer.to_account_info(),
+            account_or_mint: self.mint.to_account_info()
         };
 
         CpiContext::new(cpi_program, cpi_accounts)
     }
+}
 
+impl<'info> WithdrawFees<'info> {
     pub fn transfer_sol_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
         let cpi_program = self.system_program.to_account_info();
         let cpi_accounts = Transfer {
-            to: self.vault.to_account_info(),
-            from: self.signer.to_account_info()
+            from: self.vault.to_account_info(),
+            to: self.destination.to_account_info()
         };
 
         CpiContext::new(cpi_program, cpi_accounts)
     }
+}
 
-    pub fn mint_supply_ctx(&self, is_team: bool) -> CpiContext<'_, '_, '_, 'info, MintTo<'info>> {
+impl<'info> WithdrawTokenFees<'info> {
+    pub fn transfer_tokens_ctx(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
         let cpi_program = self.token_program.to_account_info();
-        let cpi_accounts = MintTo {
+        let cpi_accounts = TransferChecked {
+            from: self.fee_vault.to_account_info(),
+            to: self.destination.to_account_info(),
             mint: self.mint.to_account_info(),
-            to: if is_team {
-                self.team_token_account.as_ref().unwrap().to_account_info()
-            } else {
-                self.vault_token_account.to_account_info()
-            },
-            authority: self.signer.to_account_info()
+            authority: self.config.to_account_info()
         };
 
         CpiContext::new(cpi_program, cpi_accounts)
@@ -553,7 +4616,7 @@ impl<'info> InitializeDao<'info> {
         let cpi_program = self.token_program.to_account_info();
         let cpi_accounts = MintTo {
             to: self.dao_token_account.to_account_info(),
-            authority: self.signer.to_account_info(),
+            authority: self.project.to_account_info(),
             mint: self.mint.to_account_info()          
         };
 
@@ -562,6 +4625,17 @@ impl<'info> InitializeDao<'info> {
 }
 
 impl<'info> AirdropTokens<'info> {
+    pub fn check_operator(&self) -> Result<()> {
+        if self.signer.key() == self.project.creator {
+            return Ok(());
+        }
+
+        let role = self.role.as_ref().ok_or(Errors::Unauthorized)?;
+        require_keys_eq!(role.granted_by, self.project.creator, Errors::Unauthorized);
+        require!(role.roles & ROLE_AIRDROP != 0, Errors::Unauthorized);
+        Ok(())
+    }
+
     pub fn transfer_tokens_ctx(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
         let cpi_program = self.token_program.to_account_info();
         let cpi_accounts = TransferChecked {
@@ -575,7 +4649,257 @@ impl<'info> AirdropTokens<'info> {
     }
 }
 
+impl<'info> CloseAirdrop<'info> {
+    pub fn transfer_tokens_ctx(&self, to: AccountInfo<'info>) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.vault_token_account.to_account_info(),
+            to,
+            mint: self.mint.to_account_info(),
+            authority: self.vault.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    pub fn burn_tokens_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = Burn {
+            mint: self.mint.to_account_info(),
+            from: self.vault_token_account.to_account_info(),
+            authority: self.vault.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> BurnVaultTokens<'info> {
+    pub fn burn_tokens_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = Burn {
+            mint: self.mint.to_account_info(),
+            from: self.vault_token_account.to_account_info(),
+            authority: self.vault.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> SweepToTreasury<'info> {
+    pub fn transfer_tokens_ctx(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.vault_token_account.to_account_info(),
+            to: self.dao_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.vault.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> Contribute<'info> {
+    pub fn transfer_sol_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            to: self.sale.to_account_info(),
+            from: self.signer.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> ClaimSaleTokens<'info> {
+    pub fn transfer_tokens_ctx(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.vault_token_account.to_account_info(),
+            to: self.contributor_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.vault.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> PlaceBid<'info> {
+    pub fn transfer_sol_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            to: self.auction.to_account_info(),
+            from: self.signer.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> ClaimAuctionTokens<'info> {
+    pub fn transfer_tokens_ctx(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.vault_token_account.to_account_info(),
+            to: self.bidder_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.vault.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> InitializeLp<'info> {
+    pub fn transfer_tokens_ctx(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.vault_token_account.to_account_info(),
+            to: self.creator_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            authority: self.vault.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> LockLp<'info> {
+    pub fn transfer_lp_ctx(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.source.to_account_info(),
+            to: self.escrow.to_account_info(),
+            mint: self.lp_mint.to_account_info(),
+            authority: self.signer.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> BurnLockedLp<'info> {
+    pub fn burn_lp_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = Burn {
+            mint: self.lp_mint.to_account_info(),
+            from: self.escrow.to_account_info(),
+            authority: self.lp_lock.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    pub fn close_escrow_ctx(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = CloseAccount {
+            account: self.escrow.to_account_info(),
+            destination: self.signer.to_account_info(),
+            authority: self.lp_lock.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> UnlockLp<'info> {
+    pub fn transfer_lp_ctx(&self) -> CpiContext<'_, '_, '_, 'info, TransferChecked<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = TransferChecked {
+            from: self.escrow.to_account_info(),
+            to: self.destination.to_account_info(),
+            mint: self.lp_mint.to_account_info(),
+            authority: self.lp_lock.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    pub fn close_escrow_ctx(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = CloseAccount {
+            account: self.escrow.to_account_info(),
+            destination: self.signer.to_account_info(),
+            authority: self.lp_lock.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> CloseProject<'info> {
+    /// Fails while anything still holds tokens for, or depends on, the project.
+    pub fn check_closable(&self) -> Result<()> {
+        let project = &self.project;
+
+        require!(self.sale.data_is_empty(), Errors::SaleNotClosed);
+        require!(self.auction.data_is_empty(), Errors::AuctionNotClosed);
+        require_eq!(project.reserved_tokens, 0, Errors::SaleNotClosed);
+        require!(matches!(project.lp_lock, LpLockStatus::Unlocked | LpLockStatus::Burned), Errors::LpLockOpen);
+        require_eq!(self.vault_token_account.amount, 0, Errors::VaultNotEmpty);
+        require!(!project.mint_exist, Errors::MintAuthorityNotBurned);
+        require!(project.dao_allocation == 0 || project.dao_init, Errors::DaoNotInitialized);
+        require!(self.profile.data_is_empty(), Errors::ProfileNotClosed);
+        Ok(())
+    }
+
+    pub fn close_vault_ctx(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = CloseAccount {
+            account: self.vault_token_account.to_account_info(),
+            destination: self.signer.to_account_info(),
+            authority: self.vault.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
 impl<'info> BurnAuthority<'info> {
+    pub fn set_auth_ctx(&self) -> CpiContext<'_, '_, '_, 'info, SetAuthority<'info>> {
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_accounts = SetAuthority {
+            current_authority: self.project.to_account_info(),
+            account_or_mint: self.mint.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> VerifyCreator<'info> {
+    pub fn sign_metadata_ctx(&self) -> CpiContext<'_, '_, '_, 'info, SignMetadata<'info>> {
+        let cpi_program = self.metadata_program.to_account_info();
+        let cpi_accounts = SignMetadata {
+            creator: self.signer.to_account_info(),
+            metadata: self.metadata.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> TransferProjectOwnership<'info> {
+    /// `authority` is whichever of the creator or the project holds the
+    /// metadata update authority.
+    pub fn update_metadata_ctx(
+        &self,
+        authority: AccountInfo<'info>
+    ) -> CpiContext<'_, '_, '_, 'info, UpdateMetadataAccountsV2<'info>> {
+        let cpi_program = self.metadata_program.to_account_info();
+        let cpi_accounts = UpdateMetadataAccountsV2 {
+            metadata: self.metadata.to_account_info(),
+            update_authority: authority
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+impl<'info> MigrateProject<'info> {
     pub fn set_auth_ctx(&self) -> CpiContext<'_, '_, '_, 'info, SetAuthority<'info>> {
         let cpi_program = self.token_program.to_account_info();
         let cpi_accounts = SetAuthority {
@@ -587,6 +4911,67 @@ impl<'info> BurnAuthority<'info> {
     }
 }
 
+impl<'info> AcceptProjectOwnership<'info> {
+    pub fn update_metadata_ctx(&self) -> CpiContext<'_, '_, '_, 'info, UpdateMetadataAccountsV2<'info>> {
+        let cpi_program = self.metadata_program.to_account_info();
+        let cpi_accounts = UpdateMetadataAccountsV2 {
+            metadata: self.metadata.to_account_info(),
+            update_authority: self.project.to_account_info()
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+#[event]
+pub struct TokensBurned {
+    pub project: Pubkey,
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub total_burned: u64,
+    pub timestamp: i64
+}
+
+#[event]
+pub struct ProjectRegistered {
+    pub id: u64,
+    pub project: Pubkey,
+    pub mint: Pubkey,
+    pub creator: Pubkey
+}
+
+#[event]
+pub struct LpLockChanged {
+    pub project: Pubkey,
+    pub lp_mint: Pubkey,
+    pub amount: u64,
+    pub unlock_at: i64,
+    pub status: LpLockStatus
+}
+
+#[event]
+pub struct LiquiditySeeded {
+    pub project: Pubkey,
+    pub amm_program: Pubkey,
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub token_amount: u64,
+    pub quote_amount: u64
+}
+
+#[event]
+pub struct ReferralPaid {
+    pub referrer: Pubkey,
+    pub project: Pubkey,
+    /// Token the share was paid in, the default key for SOL.
+    pub fee_mint: Pubkey,
+    pub amount: u64,
+    pub projects_referred: u64,
+    /// Running total the referrer has earned in `fee_mint`.
+    pub earned: u64
+}
+
 #[error_code]
 pub enum Errors {
     #[msg("council token holding account not provided")]
@@ -594,7 +4979,161 @@ pub enum Errors {
     #[msg("quorum should be in the range of 1 and 100")]
     InvalidQuorum,
     #[msg("max recipients has been paid out")]
-    MaxRecipientsPaid
+    MaxRecipientsPaid,
+    #[msg("instruction is not allowed in the current project state")]
+    InvalidStateTransition,
+    #[msg("dao allocation exceeds the limit set at launch")]
+    DaoAllocationExceeded,
+    #[msg("dao has not been initialized for this project")]
+    DaoNotInitialized,
+    #[msg("vault token account is empty")]
+    VaultEmpty,
+    #[msg("airdrop deadline must be in the future")]
+    InvalidDeadline,
+    #[msg("airdrop deadline has already been set")]
+    AirdropDeadlineSet,
+    #[msg("project has no airdrop deadline")]
+    NoAirdropDeadline,
+    #[msg("airdrop deadline has passed")]
+    AirdropEnded,
+    #[msg("airdrop deadline has not passed yet")]
+    AirdropNotEnded,
+    #[msg("airdrop has been closed")]
+    AirdropClosed,
+    #[msg("destination account for the clawback policy not provided")]
+    MissingClawbackAccount,
+    #[msg("signer is not allowed to perform this action")]
+    Unauthorized,
+    #[msg("amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("vault token account does not hold enough tokens")]
+    InsufficientVaultBalance,
+    #[msg("vault token account still holds tokens")]
+    VaultNotEmpty,
+    #[msg("mint authority has not been burned")]
+    MintAuthorityNotBurned,
+    #[msg("account is not a recognized layout")]
+    InvalidAccountVersion,
+    #[msg("account is already on the latest version")]
+    AlreadyMigrated,
+    #[msg("feature was not paid for at launch")]
+    FeatureNotPaid,
+    #[msg("basis points must be at most 10000")]
+    InvalidBasisPoints,
+    #[msg("token account does not qualify for the holder discount")]
+    DiscountNotEligible,
+    #[msg("fee overflowed")]
+    FeeOverflow,
+    #[msg("fee mint is not accepted right now")]
+    FeeMintDisabled,
+    #[msg("account needed to pay the fee in tokens not provided")]
+    MissingFeeAccount,
+    #[msg("fee account does not match the fee mint")]
+    InvalidFeeAccount,
+    #[msg("account needed to pay the referrer not provided")]
+    MissingReferrerAccount,
+    #[msg("referrer does not match the referral account")]
+    InvalidReferrer,
+    #[msg("instruction is paused by the admin")]
+    Paused,
+    #[msg("creator does not hold a launch permit")]
+    CreatorNotAllowed,
+    #[msg("launch permit has expired")]
+    PermitExpired,
+    #[msg("verifier attestation is missing or invalid")]
+    InvalidAttestation,
+    #[msg("admin set must be unique, at most 5 keys, with a threshold between 1 and its size")]
+    InvalidAdminSet,
+    #[msg("an approved proposal is required while the admin set is active")]
+    ProposalRequired,
+    #[msg("proposal has already been executed")]
+    ProposalExecuted,
+    #[msg("admin set changed since the proposal was created")]
+    ProposalStale,
+    #[msg("proposal does not have enough approvals")]
+    ProposalNotApproved,
+    #[msg("proposal is for a different action")]
+    ProposalMismatch,
+    #[msg("new creator must differ from the current one")]
+    InvalidNewCreator,
+    #[msg("creators must be 1 to 5 unique wallets with shares summing to 100")]
+    InvalidCreators,
+    #[msg("project is already in the registry")]
+    AlreadyRegistered,
+    #[msg("profile has too many socials or tags")]
+    TooManyProfileEntries,
+    #[msg("profile link is too long or a tag is empty or too long")]
+    InvalidProfileField,
+    #[msg("sale window, caps or token amount are invalid")]
+    InvalidSaleConfig,
+    #[msg("sale is not open for contributions")]
+    SaleNotActive,
+    #[msg("sale has not ended yet")]
+    SaleNotEnded,
+    #[msg("wallet is not on the sale allowlist")]
+    NotOnAllowlist,
+    #[msg("contribution is outside the per-wallet limits")]
+    InvalidContribution,
+    #[msg("contribution would exceed the hard cap")]
+    SaleHardCapReached,
+    #[msg("sale did not reach its soft cap")]
+    SoftCapNotMet,
+    #[msg("sale reached its soft cap, claim tokens instead")]
+    SoftCapMet,
+    #[msg("sale proceeds have already been withdrawn")]
+    SaleProceedsWithdrawn,
+    #[msg("sale still has unclaimed or unrefunded contributions")]
+    SaleNotSettled,
+    #[msg("auction window, prices or token amount are invalid")]
+    InvalidAuctionConfig,
+    #[msg("auction is not taking bids")]
+    AuctionNotActive,
+    #[msg("auction has not ended yet")]
+    AuctionNotEnded,
+    #[msg("auction proceeds have already been withdrawn")]
+    AuctionProceedsWithdrawn,
+    #[msg("auction still has unclaimed bids or proceeds")]
+    AuctionNotSettled,
+    #[msg("proceeds must go to the DAO treasury if there is one, else the creator")]
+    InvalidProceedsDestination,
+    #[msg("unlock time must be in the future and no earlier than the current one")]
+    InvalidUnlockTime,
+    #[msg("lp mint does not match the lock")]
+    InvalidLpMint,
+    #[msg("no lp tokens are locked for this project")]
+    LpNotLocked,
+    #[msg("lp lock is permanent")]
+    LpLockPermanent,
+    #[msg("lp tokens are still locked")]
+    LpStillLocked,
+    #[msg("amm program is not approved")]
+    AmmNotApproved,
+    #[msg("too many approved amm programs")]
+    TooManyAmmPrograms,
+    #[msg("amm accounts do not match the pool being created")]
+    InvalidAmmAccounts,
+    #[msg("liquidity has already been seeded")]
+    LpAlreadyInitialized,
+    #[msg("creator registry is full")]
+    CreatorRegistryFull,
+    #[msg("close the project profile first")]
+    ProfileNotClosed,
+    #[msg("close the sale first")]
+    SaleNotClosed,
+    #[msg("arithmetic overflow")]
+    MathOverflow,
+    #[msg("mint decimals are above the maximum")]
+    InvalidDecimals,
+    #[msg("close the auction first")]
+    AuctionNotClosed,
+    #[msg("unlock the LP tokens first")]
+    LpLockOpen,
+    #[msg("liquidity has not been seeded yet")]
+    LpNotInitialized,
+    #[msg("the project is already finalized")]
+    ProjectFinalized,
+    #[msg("register on the first creator registry page with room")]
+    InvalidRegistryPage
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -672,4 +5211,99 @@ pub enum SetRealmAuthorityAction {
     SetUnchecked,
     SetChecked,
     Remove
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_project() -> ProjectV0 {
+        ProjectV0 {
+            creator: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            dao_init: false,
+            lp_init: false,
+            mint_exist: true,
+            bump: 255,
+            recipients: 10,
+            recipients_paid: 0
+        }
+    }
+
+    fn legacy_config() -> ConfigV0 {
+        ConfigV0 {
+            fee: 1_000_000,
+            creator: Pubkey::new_unique(),
+            bump: 255
+        }
+    }
+
+    fn account_size<T: AccountSerialize>(account: &T) -> usize {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data.len()
+    }
+
+    #[test]
+    fn project_fits_allocation() {
+        let project = Project::from_legacy(legacy_project());
+        assert_eq!(account_size(&project), 8 + Project::INIT_SPACE);
+    }
+
+    #[test]
+    fn config_fits_allocation() {
+        let config = Config::from_legacy(legacy_config());
+        assert_eq!(account_size(&config), 8 + Config::INIT_SPACE);
+    }
+
+    #[test]
+    fn creator_registry_fits_allocation_when_full() {
+        let mut registry = CreatorRegistry {
+            creator: Pubkey::new_unique(),
+            bump: 255,
+            projects: Vec::new()
+        };
+
+        for id in 0..MAX_CREATOR_PROJECTS as u64 {
+            registry.append(registry.creator, registry.bump, id).unwrap();
+        }
+        assert!(registry.append(registry.creator, registry.bump, 0).is_err());
+        assert_eq!(account_size(&registry), 8 + CreatorRegistry::INIT_SPACE);
+    }
+
+    #[test]
+    fn auction_cost_rounds_up_and_checks_overflow() {
+        let mut auction = Auction {
+            project: Pubkey::new_unique(),
+            start: 0,
+            end: 1000,
+            start_price: 3,
+            floor_price: 1,
+            decay_interval: 100,
+            token_amount: 10,
+            decimals: 1,
+            tokens_committed: 0,
+            total_deposited: 0,
+            clearing_price: 0,
+            bidders: 0,
+            settled: 0,
+            tokens_claimed: 0,
+            proceeds_withdrawn: false,
+            bump: 255
+        };
+
+        assert_eq!(auction.cost(5, 3).unwrap(), 2);
+        assert!(auction.cost(u64::MAX, 20).is_err());
+
+        auction.decimals = 39;
+        assert!(auction.cost(1, 1).is_err());
+    }
+
+    #[test]
+    fn legacy_layouts_match_deployed_sizes() {
+        assert_eq!(legacy_project().try_to_vec().unwrap().len() + 8, LEGACY_PROJECT_LEN);
+        assert_eq!(legacy_config().try_to_vec().unwrap().len() + 8, LEGACY_CONFIG_LEN);
+        assert_eq!(LEGACY_PROJECT_LEN, 80);
+        assert_eq!(LEGACY_CONFIG_LEN, 49);
+    }
+}
@@ -0,0 +1,425 @@
+#![allow(dead_code)]
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, AnchorSerialize, Discriminator, InstructionData, ToAccountMetas};
+use anchor_spl::{
+    associated_token::get_associated_token_address, metadata::mpl_token_metadata,
+    token::spl_token::{self, state::{Account as TokenAccount, Mint}}
+};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account, clock::Clock, compute_budget::ComputeBudgetInstruction, instruction::{Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL, program_option::COption, program_pack::Pack, rent::Rent, signature::Keypair,
+    signer::Signer, transaction::{Transaction, TransactionError}
+};
+use tatami_client::{
+    instructions::{self, CpSwapPool, DaoAddresses, InitProjectArgs, InitializeDaoArgs, LaunchOptions}, pda, AmmKind,
+    Auction, AuctionArgs, ClawbackPolicy, Config, CreatorPermit, CreatorRegistry, Errors, LpLock, Project, ProjectIndex,
+    ProjectProfile, Referral, ReferralEarnings, Sale, SaleArgs, SeedLiquidityArgs, FEATURE_DAO, FEATURE_LP,
+    MAX_CREATOR_PROJECTS
+};
+use tatami_v2::{ConfigV0, ProjectV0, REALMS_ID};
+
+pub const FEE: u64 = 1_000_000;
+pub const DECIMALS: u8 = 6;
+pub const TEAM_SUPPLY: u64 = 1_000_000;
+pub const AIRDROP_SUPPLY: u64 = 10_000_000;
+pub const DAO_ALLOCATION: u64 = 5_000_000;
+
+/// Tatami, the mock AMM, Token Metadata and SPL Governance loaded from `.so`
+/// files. The first two come from `cargo build-sbf`, the others from
+/// `tests/fixtures` (see `scripts/dump-fixtures.sh`).
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new("tatami_v2", tatami_v2::ID, None);
+    program_test.prefer_bpf(true);
+    program_test.add_program("mock_amm", mock_amm::ID, None);
+    program_test.add_program("mpl_token_metadata", mpl_token_metadata::ID, None);
+    program_test.add_program("spl_governance", REALMS_ID, None);
+    program_test
+}
+
+pub struct TestEnv {
+    pub ctx: ProgramTestContext,
+    /// Platform admin, also the default project creator.
+    pub admin: Keypair
+}
+
+impl TestEnv {
+    /// Starts the runtime with the config already created.
+    pub async fn new() -> Self {
+        Self::with(program_test(), true).await
+    }
+
+    pub async fn with(program_test: ProgramTest, create_config: bool) -> Self {
+        let ctx = program_test.start_with_context().await;
+        let admin = clone_keypair(&ctx.payer);
+        let mut env = Self { ctx, admin };
+
+        if create_config {
+            env.send(&[instructions::create_config(env.admin.pubkey(), FEE)], &[]).await.unwrap();
+        }
+
+        env
+    }
+
+    /// Sends `ixs` paid by the admin, plus any extra `signers`.
+    pub async fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+
+        let mut all_signers = vec![&self.admin];
+        all_signers.extend_from_slice(signers);
+
+        let transaction = Transaction::new_signed_with_payer(ixs, Some(&self.admin.pubkey()), &all_signers, blockhash);
+        self.ctx.banks_client.process_transaction(transaction).await
+    }
+
+    /// Sends `ixs` paid by `payer` instead of the admin.
+    pub async fn send_as(&mut self, payer: &Keypair, ixs: &[Instruction]) -> Result<(), BanksClientError> {
+        let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &[payer], blockhash);
+        self.ctx.banks_client.process_transaction(transaction).await
+    }
+
+    pub async fn fund(&mut self, to: &Pubkey, lamports: u64) {
+        let ix = solana_sdk::system_instruction::transfer(&self.admin.pubkey(), to, lamports);
+        self.send(&[ix], &[]).await.unwrap();
+    }
+
+    /// Approves the mock AMM and creates the config its pools live under.
+    pub async fn setup_amm(&mut self) {
+        let create_amm_config = Instruction {
+            program_id: mock_amm::ID,
+            accounts: mock_amm::accounts::CreateAmmConfig {
+                signer: self.admin.pubkey(),
+                amm_config: amm_config(),
+                system_program: solana_sdk::system_program::ID
+            }.to_account_metas(None),
+            data: mock_amm::instruction::CreateAmmConfig { index: 0 }.data()
+        };
+        let approve = instructions::approve_amm(self.admin.pubkey(), mock_amm::ID, AmmKind::CpSwap);
+
+        self.send(&[create_amm_config, approve], &[]).await.unwrap();
+    }
+
+    /// Launches a project with the default supplies and returns its mint.
+    pub async fn launch(&mut self, args: InitProjectArgs) -> Keypair {
+        let mint = Keypair::new();
+        let ix = instructions::init_project(self.admin.pubkey(), mint.pubkey(), Some(self.admin.pubkey()), args);
+
+        self.send(&[ix], &[&mint]).await.unwrap();
+        mint
+    }
+
+    /// Launches with optional accounts, returning the mint even on failure.
+    pub async fn try_launch(&mut self, args: InitProjectArgs, options: LaunchOptions) -> (Result<(), BanksClientError>, Keypair) {
+        let mint = Keypair::new();
+        let ix = instructions::init_project_with(self.admin.pubkey(), mint.pubkey(), Some(self.admin.pubkey()), args, options);
+
+        (self.send(&[ix], &[&mint]).await, mint)
+    }
+
+    /// Launches a project paying for LP and seeds a mock AMM pool for it
+    /// against a fresh quote token. Needs `setup_amm` first.
+    pub async fn launch_with_pool(&mut self, args: InitProjectArgs) -> (Pubkey, CpSwapPool) {
+        let mint = self.launch(InitProjectArgs { features: args.features | FEATURE_LP, ..args }).await.pubkey();
+        let quote_mint = self.launch_default().await.pubkey();
+        let pool = CpSwapPool::derive(mock_amm::ID, amm_config(), &mint, &quote_mint);
+
+        let creator = self.admin.pubkey();
+        let amm_accounts = pool.accounts(&creator, Pubkey::new_unique());
+        let seed = instructions::initialize_lp(creator, mint, mock_amm::ID, amm_accounts, seed_args());
+        let budget = ComputeBudgetInstruction::set_compute_unit_limit(400_000);
+
+        self.send(&[budget, seed], &[]).await.unwrap();
+        (mint, pool)
+    }
+
+    /// Lamports collected by the platform vault so far.
+    pub async fn vault_lamports(&mut self) -> u64 {
+        self.account(&pda::vault().0).await.map_or(0, |account| account.lamports)
+    }
+
+    pub async fn launch_default(&mut self) -> Keypair {
+        self.launch(launch_args()).await
+    }
+
+    /// Creates a DAO for the project without a council.
+    pub async fn init_dao(&mut self, mint: &Pubkey, name: &str, supply: u64) -> (Result<(), BanksClientError>, DaoAddresses) {
+        let governed_account = Keypair::new().pubkey();
+        let dao = DaoAddresses::derive(name, mint, None, &governed_account);
+
+        let ix = instructions::initialize_dao(self.admin.pubkey(), *mint, None, governed_account, dao_args(name, supply));
+        (self.send(&[ix], &[]).await, dao)
+    }
+
+    pub async fn account(&mut self, address: &Pubkey) -> Option<Account> {
+        self.ctx.banks_client.get_account(*address).await.unwrap()
+    }
+
+    pub async fn project(&mut self, mint: &Pubkey) -> Project {
+        let account = self.account(&pda::project(mint).0).await.expect("project missing");
+        Project::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub async fn config(&mut self) -> Config {
+        let account = self.account(&pda::config().0).await.expect("config missing");
+        Config::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    /// Registry id the next registered project gets.
+    pub async fn next_project_id(&mut self) -> u64 {
+        self.config().await.project_count
+    }
+
+    /// Adds the project of `mint` to the registry under the next id, on the
+    /// first page of its creator's list with room.
+    pub async fn register(&mut self, mint: &Pubkey) -> Result<(), BanksClientError> {
+        let creator = self.project(mint).await.creator;
+        let id = self.next_project_id().await;
+
+        let mut page = 0;
+        while let Some(registry) = self.creator_registry_page(&creator, page).await {
+            if registry.projects.len() < MAX_CREATOR_PROJECTS {
+                break;
+            }
+            page += 1;
+        }
+
+        self.send(&[instructions::register_project(self.admin.pubkey(), *mint, creator, id, page)], &[]).await
+    }
+
+    pub async fn project_index(&mut self, id: u64) -> ProjectIndex {
+        let account = self.account(&pda::project_index(id).0).await.expect("project index missing");
+        ProjectIndex::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub async fn project_profile(&mut self, mint: &Pubkey) -> Option<ProjectProfile> {
+        let account = self.account(&pda::project_profile(&pda::project(mint).0).0).await?;
+        Some(ProjectProfile::try_deserialize(&mut &account.data[..]).unwrap())
+    }
+
+    pub async fn sale(&mut self, mint: &Pubkey) -> Option<Sale> {
+        let account = self.account(&pda::sale(&pda::project(mint).0).0).await?;
+        Some(Sale::try_deserialize(&mut &account.data[..]).unwrap())
+    }
+
+    pub async fn auction(&mut self, mint: &Pubkey) -> Option<Auction> {
+        let account = self.account(&pda::auction(&pda::project(mint).0).0).await?;
+        Some(Auction::try_deserialize(&mut &account.data[..]).unwrap())
+    }
+
+    pub async fn lp_lock(&mut self, mint: &Pubkey) -> Option<LpLock> {
+        let account = self.account(&pda::lp_lock(&pda::project(mint).0).0).await?;
+        Some(LpLock::try_deserialize(&mut &account.data[..]).unwrap())
+    }
+
+    pub async fn creator_registry(&mut self, creator: &Pubkey) -> CreatorRegistry {
+        self.creator_registry_page(creator, 0).await.expect("creator registry missing")
+    }
+
+    pub async fn creator_registry_page(&mut self, creator: &Pubkey, page: u16) -> Option<CreatorRegistry> {
+        let account = self.account(&pda::creator_registry(creator, page).0).await?;
+        Some(CreatorRegistry::try_deserialize(&mut &account.data[..]).unwrap())
+    }
+
+    /// Overwrites page `page` of `creator`'s registry list with `projects`.
+    pub fn set_creator_registry(&mut self, creator: &Pubkey, page: u16, projects: Vec<u64>) {
+        let (address, bump) = pda::creator_registry(creator, page);
+        let registry = CreatorRegistry { creator: *creator, bump, projects };
+        self.ctx.set_account(&address, &program_account(CreatorRegistry::DISCRIMINATOR, registry).into());
+    }
+
+    pub async fn referral(&mut self, referrer: &Pubkey) -> Referral {
+        let account = self.account(&pda::referral(referrer).0).await.expect("referral missing");
+        Referral::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub async fn referral_earnings(&mut self, referrer: &Pubkey, mint: &Pubkey) -> ReferralEarnings {
+        let account = self.account(&pda::referral_earnings(referrer, mint).0).await.expect("referral earnings missing");
+        ReferralEarnings::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub async fn creator_permit(&mut self, creator: &Pubkey) -> CreatorPermit {
+        let account = self.account(&pda::creator_permit(creator).0).await.expect("permit missing");
+        CreatorPermit::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub async fn metadata(&mut self, mint: &Pubkey) -> mpl_token_metadata::accounts::Metadata {
+        let account = self.account(&pda::metadata(mint)).await.expect("metadata missing");
+        mpl_token_metadata::accounts::Metadata::safe_deserialize(&account.data).unwrap()
+    }
+
+    pub async fn token_balance(&mut self, address: &Pubkey) -> u64 {
+        let account = self.account(address).await.expect("token account missing");
+        TokenAccount::unpack(&account.data).unwrap().amount
+    }
+
+    pub async fn mint_state(&mut self, mint: &Pubkey) -> Mint {
+        let account = self.account(mint).await.expect("mint missing");
+        Mint::unpack(&account.data).unwrap()
+    }
+
+    pub async fn vault_balance(&mut self, mint: &Pubkey) -> u64 {
+        self.token_balance(&pda::vault_token_account(mint)).await
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp
+    }
+
+    pub async fn warp_to_timestamp(&mut self, unix_timestamp: i64) {
+        let mut clock = self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        self.ctx.set_sysvar(&clock);
+    }
+}
+
+pub fn launch_args() -> InitProjectArgs {
+    InitProjectArgs {
+        decimals: DECIMALS,
+        name: "Tatami Coin".to_string(),
+        symbol: "TTM".to_string(),
+        uri: String::new(),
+        recipients: 10,
+        supply: [TEAM_SUPPLY, AIRDROP_SUPPLY],
+        dao_allocation: DAO_ALLOCATION,
+        airdrop_deadline: 0,
+        clawback_policy: ClawbackPolicy::Creator,
+        features: FEATURE_DAO,
+        creators: vec![],
+        collection: None
+    }
+}
+
+/// Sale of half the airdrop supply raising 1 to 3 SOL, open from `start`
+/// for an hour.
+pub fn sale_args(start: i64, merkle_root: [u8; 32]) -> SaleArgs {
+    SaleArgs {
+        start,
+        end: start + 3600,
+        soft_cap: LAMPORTS_PER_SOL,
+        hard_cap: 3 * LAMPORTS_PER_SOL,
+        min_contribution: LAMPORTS_PER_SOL / 10,
+        max_contribution: 2 * LAMPORTS_PER_SOL,
+        merkle_root,
+        token_amount: AIRDROP_SUPPLY / 2
+    }
+}
+
+/// Auction of half the airdrop supply from 2 SOL down to 0.5 SOL a token
+/// over 1000 seconds, dropping every 100.
+pub fn auction_args(start: i64) -> AuctionArgs {
+    AuctionArgs {
+        start,
+        end: start + 1000,
+        start_price: 2 * LAMPORTS_PER_SOL,
+        floor_price: LAMPORTS_PER_SOL / 2,
+        decay_interval: 100,
+        token_amount: AIRDROP_SUPPLY / 2
+    }
+}
+
+/// Seeds 2 tokens against 0.5 quote tokens.
+pub fn seed_args() -> SeedLiquidityArgs {
+    SeedLiquidityArgs { token_amount: 2_000_000, quote_amount: 500_000, open_time: 0 }
+}
+
+/// LP tokens the creator gets for `seed_args`: sqrt(2_000_000 * 500_000),
+/// less the liquidity the mock AMM locks.
+pub const LP_TOKENS: u64 = 1_000_000 - mock_amm::LOCKED_LIQUIDITY;
+
+/// Mock AMM config the tests create pools under.
+pub fn amm_config() -> Pubkey {
+    Pubkey::find_program_address(&[mock_amm::AMM_CONFIG_SEED, &0u16.to_be_bytes()], &mock_amm::ID).0
+}
+
+pub fn dao_args(name: &str, supply: u64) -> InitializeDaoArgs {
+    InitializeDaoArgs {
+        name: name.to_string(),
+        supply,
+        min_vote_to_govern: 1_000,
+        quorum: 60,
+        vote_duration: 86_400
+    }
+}
+
+pub fn clone_keypair(keypair: &Keypair) -> Keypair {
+    Keypair::from_bytes(&keypair.to_bytes()).unwrap()
+}
+
+pub fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(owner, mint)
+}
+
+/// Asserts that the transaction failed with the given program error.
+pub fn assert_error(result: Result<(), BanksClientError>, expected: Errors) {
+    let code: u32 = expected.into();
+
+    match result.expect_err("transaction should have failed").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(actual)) => {
+            assert_eq!(actual, code, "expected {expected:?}")
+        },
+        other => panic!("expected {expected:?}, got {other:?}")
+    }
+}
+
+/// Program-owned account holding `data` behind the account discriminator.
+pub fn program_account(discriminator: [u8; 8], data: impl AnchorSerialize) -> Account {
+    let mut bytes = discriminator.to_vec();
+    data.serialize(&mut bytes).unwrap();
+
+    Account {
+        lamports: Rent::default().minimum_balance(bytes.len()),
+        data: bytes,
+        owner: tatami_v2::ID,
+        executable: false,
+        rent_epoch: 0
+    }
+}
+
+pub fn mint_account(authority: &Pubkey) -> Account {
+    let mut data = vec![0; Mint::LEN];
+    Mint {
+        mint_authority: COption::Some(*authority),
+        supply: 0,
+        decimals: DECIMALS,
+        is_initialized: true,
+        freeze_authority: COption::None
+    }.pack_into_slice(&mut data);
+
+    Account {
+        lamports: Rent::default().minimum_balance(Mint::LEN),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0
+    }
+}
+
+/// Adds a project in the layout used before versioning.
+pub fn add_legacy_project(program_test: &mut ProgramTest, creator: &Pubkey, mint: &Pubkey) {
+    let (project, bump) = pda::project(mint);
+
+    program_test.add_account(*mint, mint_account(creator));
+    program_test.add_account(project, program_account(Project::DISCRIMINATOR, ProjectV0 {
+        creator: *creator,
+        mint: *mint,
+        dao_init: false,
+        lp_init: false,
+        mint_exist: true,
+        bump,
+        recipients: 10,
+        recipients_paid: 3
+    }));
+}
+
+/// Adds a config in the layout used before versioning.
+pub fn add_legacy_config(program_test: &mut ProgramTest, creator: &Pubkey) {
+    let (config, bump) = pda::config();
+
+    program_test.add_account(config, program_account(Config::DISCRIMINATOR, ConfigV0 {
+        fee: FEE,
+        creator: *creator,
+        bump
+    }));
+}
@@ -0,0 +1,196 @@
+#![cfg(feature = "test-sbf")]
+
+//! Drives random sequences of launches, airdrops, DAO creation and authority
+//! burns against the runtime, checking supply accounting after every step.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+use solana_program_test::tokio::runtime::Builder;
+use solana_sdk::{signature::Keypair, signer::Signer};
+use tatami_client::{instructions::{self, InitProjectArgs}, ProjectState};
+
+use common::*;
+
+#[derive(Debug, Clone)]
+enum Op {
+    Launch { recipients: u16, team_supply: u64, airdrop_supply: u64, dao_allocation: u64 },
+    Airdrop { project: usize, receiver: usize, amount: u64 },
+    UnauthorizedAirdrop { project: usize, amount: u64 },
+    InitDao { project: usize, supply: u64 },
+    BurnAuthority { project: usize }
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        1 => (1u16..5, 0u64..1_000_000, 1u64..1_000_000, 0u64..1_000_000).prop_map(
+            |(recipients, team_supply, airdrop_supply, dao_allocation)| {
+                Op::Launch { recipients, team_supply, airdrop_supply, dao_allocation }
+            }
+        ),
+        4 => (any::<usize>(), 0usize..3, 0u64..600_000).prop_map(
+            |(project, receiver, amount)| Op::Airdrop { project, receiver, amount }
+        ),
+        1 => (any::<usize>(), 0u64..600_000).prop_map(
+            |(project, amount)| Op::UnauthorizedAirdrop { project, amount }
+        ),
+        1 => (any::<usize>(), 0u64..1_000_000).prop_map(|(project, supply)| Op::InitDao { project, supply }),
+        1 => any::<usize>().prop_map(|project| Op::BurnAuthority { project })
+    ]
+}
+
+/// What the chain should look like for one project.
+struct Model {
+    mint: Pubkey,
+    recipients: u16,
+    recipients_paid: u16,
+    team_supply: u64,
+    airdrop_supply: u64,
+    vault: u64,
+    dao_allocation: u64,
+    dao_minted: u64,
+    state: ProjectState
+}
+
+impl Model {
+    fn can_airdrop(&self, amount: u64) -> bool {
+        self.state != ProjectState::Finalized && self.recipients_paid < self.recipients && amount <= self.vault
+    }
+
+    fn can_init_dao(&self, supply: u64) -> bool {
+        matches!(self.state, ProjectState::Created | ProjectState::Distributed) && supply <= self.dao_allocation
+    }
+
+    fn can_burn_authority(&self) -> bool {
+        match self.state {
+            ProjectState::Created | ProjectState::Distributed => self.dao_allocation == 0,
+            ProjectState::DaoLive => true,
+            ProjectState::Finalized => false
+        }
+    }
+}
+
+async fn check(env: &mut TestEnv, model: &Model) {
+    let project = env.project(&model.mint).await;
+    let mint = env.mint_state(&model.mint).await;
+
+    assert!(project.recipients_paid <= project.recipients);
+    assert_eq!(project.recipients_paid, model.recipients_paid);
+    assert_eq!(project.state, model.state);
+    assert_eq!(mint.supply, model.team_supply + model.airdrop_supply + model.dao_minted);
+    assert_eq!(env.vault_balance(&model.mint).await, model.vault);
+    assert_eq!(mint.mint_authority.is_some(), model.state != ProjectState::Finalized);
+
+    // A finalized project never waits on a DAO it can no longer mint for.
+    if project.state == ProjectState::Finalized {
+        assert!(project.dao_allocation == 0 || project.dao_init);
+    }
+}
+
+async fn run(ops: Vec<Op>) {
+    let mut env = TestEnv::new().await;
+    let receivers = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    let mut models: Vec<Model> = Vec::new();
+    let mut daos = 0;
+
+    let stranger = Keypair::new();
+    env.fund(&stranger.pubkey(), 1_000_000_000).await;
+
+    for op in ops {
+        let touched = match op {
+            Op::Launch { recipients, team_supply, airdrop_supply, dao_allocation } => {
+                let mint = env.launch(InitProjectArgs {
+                    recipients,
+                    supply: [team_supply, airdrop_supply],
+                    dao_allocation,
+                    ..launch_args()
+                }).await.pubkey();
+
+                models.push(Model {
+                    mint,
+                    recipients,
+                    recipients_paid: 0,
+                    team_supply,
+                    airdrop_supply,
+                    vault: airdrop_supply,
+                    dao_allocation,
+                    dao_minted: 0,
+                    state: ProjectState::Created
+                });
+                models.len() - 1
+            },
+            Op::Airdrop { project, receiver, amount } if !models.is_empty() => {
+                let index = project % models.len();
+                let model = &mut models[index];
+
+                let ix = instructions::airdrop_tokens(env.admin.pubkey(), model.mint, receivers[receiver], amount);
+                let result = env.send(&[ix], &[]).await;
+
+                assert_eq!(result.is_ok(), model.can_airdrop(amount), "airdrop {amount}: {result:?}");
+                if result.is_ok() {
+                    model.vault -= amount;
+                    model.recipients_paid += 1;
+                    if model.state == ProjectState::Created {
+                        model.state = ProjectState::Distributed;
+                    }
+                }
+                index
+            },
+            Op::UnauthorizedAirdrop { project, amount } if !models.is_empty() => {
+                let index = project % models.len();
+                let model = &models[index];
+
+                let ix = instructions::airdrop_tokens(stranger.pubkey(), model.mint, stranger.pubkey(), amount);
+                assert!(env.send_as(&stranger, &[ix]).await.is_err());
+                index
+            },
+            Op::InitDao { project, supply } if !models.is_empty() => {
+                let index = project % models.len();
+                let model = &mut models[index];
+
+                daos += 1;
+                let (result, _) = env.init_dao(&model.mint, &format!("DAO {daos}"), supply).await;
+
+                assert_eq!(result.is_ok(), model.can_init_dao(supply), "init dao {supply}: {result:?}");
+                if result.is_ok() {
+                    model.dao_minted = supply;
+                    model.state = ProjectState::DaoLive;
+                }
+                index
+            },
+            Op::BurnAuthority { project } if !models.is_empty() => {
+                let index = project % models.len();
+                let model = &mut models[index];
+
+                let result = env.send(&[instructions::burn_authority(env.admin.pubkey(), model.mint)], &[]).await;
+
+                assert_eq!(result.is_ok(), model.can_burn_authority(), "burn authority: {result:?}");
+                if result.is_ok() {
+                    model.state = ProjectState::Finalized;
+                }
+                index
+            },
+            _ => continue
+        };
+
+        check(&mut env, &models[touched]).await;
+    }
+
+    for model in &models {
+        check(&mut env, model).await;
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 24,
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn supply_accounting_holds(ops in prop::collection::vec(op(), 1..24)) {
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(run(ops));
+    }
+}
@@ -0,0 +1,1688 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use std::slice;
+
+use anchor_lang::{prelude::Pubkey, InstructionData, Space, ToAccountMetas};
+use anchor_spl::{metadata::mpl_token_metadata::accounts::Metadata, token::spl_token};
+use solana_program_test::tokio;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::{AccountMeta, Instruction}, native_token::LAMPORTS_PER_SOL,
+    program_option::COption, signature::Keypair, signer::Signer, system_program, sysvar
+};
+use tatami_client::{
+    accounts::{decode_project, ProjectAccount}, allowlist::AllowlistTree,
+    instructions::{self, CpSwapPool, DaoAddresses, InitProjectArgs, LaunchOptions}, pda, permit_message, AdminAction, AmmKind, AuctionArgs, ClawbackPolicy, Config, CreatorShare, Errors, FeeSchedule,
+    LpLockStatus, ProfileData, Project, ProjectCategory, ProjectState, SaleArgs, FEATURE_DAO,
+    FEATURE_LP, MAX_CREATOR_PROJECTS, PAUSE_AIRDROP, PAUSE_INIT_LP, ROLE_AIRDROP
+};
+use tatami_v2::{accounts, instruction, CONFIG_VERSION, MAX_DECIMALS, PROJECT_VERSION, REALMS_ID};
+
+use common::*;
+
+fn close_airdrop_ix(
+    signer: Pubkey,
+    project: &Project,
+    creator_token_account: Option<Pubkey>,
+    dao_token_account: Option<Pubkey>
+) -> Instruction {
+    Instruction {
+        program_id: tatami_v2::ID,
+        accounts: accounts::CloseAirdrop {
+            signer,
+            project: pda::project(&project.mint).0,
+            vault: pda::vault().0,
+            vault_token_account: pda::vault_token_account(&project.mint),
+            creator: project.creator,
+            creator_token_account,
+            dao_token_account,
+            mint: project.mint,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID
+        }.to_account_metas(None),
+        data: instruction::CloseAirdrop {}.data()
+    }
+}
+
+async fn launch_with_deadline(env: &mut TestEnv, clawback_policy: ClawbackPolicy) -> (Keypair, i64) {
+    let deadline = env.now().await + 3_600;
+    let mint = env.launch(InitProjectArgs {
+        airdrop_deadline: deadline,
+        clawback_policy,
+        ..launch_args()
+    }).await;
+
+    (mint, deadline)
+}
+
+#[tokio::test]
+async fn create_and_update_config() {
+    let mut env = TestEnv::new().await;
+
+    let config = env.config().await;
+    assert_eq!(config.version, CONFIG_VERSION);
+    assert_eq!(config.creator, env.admin.pubkey());
+    assert_eq!(config.fee, FEE);
+
+    env.send(&[instructions::update_config(env.admin.pubkey(), 42)], &[]).await.unwrap();
+    assert_eq!(env.config().await.fee, 42);
+
+    let stranger = Keypair::new();
+    env.fund(&stranger.pubkey(), 1_000_000_000).await;
+    assert!(env.send_as(&stranger, &[instructions::update_config(stranger.pubkey(), 0)]).await.is_err());
+}
+
+#[tokio::test]
+async fn init_project_mints_supply_and_charges_fee() {
+    let mut env = TestEnv::new().await;
+    let vault_lamports = env.vault_lamports().await;
+
+    let mint = env.launch_default().await.pubkey();
+
+    let project = env.project(&mint).await;
+    assert_eq!(project.version, PROJECT_VERSION);
+    assert_eq!(project.state, ProjectState::Created);
+    assert_eq!(project.creator, env.admin.pubkey());
+    assert_eq!(project.dao_allocation, DAO_ALLOCATION);
+    assert!(project.mint_exist);
+
+    assert_eq!(env.vault_balance(&mint).await, AIRDROP_SUPPLY);
+    assert_eq!(env.token_balance(&ata(&env.admin.pubkey(), &mint)).await, TEAM_SUPPLY);
+    assert_eq!(env.vault_lamports().await, vault_lamports + FEE);
+    assert_eq!(project.fee_paid, FEE);
+
+    assert_eq!(env.mint_state(&mint).await.mint_authority, COption::Some(pda::project(&mint).0));
+    let admin = env.admin.pubkey();
+    let direct_mint = spl_token::instruction::mint_to(&spl_token::ID, &mint, &ata(&admin, &mint), &admin, &[], 1).unwrap();
+    assert!(env.send(&[direct_mint], &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn init_project_rejects_past_deadline() {
+    let mut env = TestEnv::new().await;
+    let mint = Keypair::new();
+
+    let ix = instructions::init_project(env.admin.pubkey(), mint.pubkey(), None, InitProjectArgs {
+        airdrop_deadline: 1,
+        ..launch_args()
+    });
+
+    assert_error(env.send(&[ix], &[&mint]).await, Errors::InvalidDeadline);
+}
+
+#[tokio::test]
+async fn init_project_caps_decimals() {
+    let mut env = TestEnv::new().await;
+
+    let args = InitProjectArgs { decimals: MAX_DECIMALS + 1, ..launch_args() };
+    let (result, _) = env.try_launch(args, LaunchOptions::default()).await;
+    assert_error(result, Errors::InvalidDecimals);
+}
+
+#[tokio::test]
+async fn airdrop_pays_recipients_until_the_limit() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch(InitProjectArgs {
+        recipients: 1,
+        ..launch_args()
+    }).await.pubkey();
+
+    let receiver = Pubkey::new_unique();
+    env.send(&[instructions::airdrop_tokens(env.admin.pubkey(), mint, receiver, 1_000)], &[]).await.unwrap();
+
+    assert_eq!(env.token_balance(&ata(&receiver, &mint)).await, 1_000);
+    assert_eq!(env.vault_balance(&mint).await, AIRDROP_SUPPLY - 1_000);
+
+    let project = env.project(&mint).await;
+    assert_eq!(project.recipients_paid, 1);
+    assert_eq!(project.state, ProjectState::Distributed);
+
+    let ix = instructions::airdrop_tokens(env.admin.pubkey(), mint, Pubkey::new_unique(), 1_000);
+    assert_error(env.send(&[ix], &[]).await, Errors::MaxRecipientsPaid);
+}
+
+#[tokio::test]
+async fn initialize_dao_mints_allocation_to_treasury() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+
+    let (result, dao) = env.init_dao(&mint, "Tatami DAO", DAO_ALLOCATION).await;
+    result.unwrap();
+
+    let project = env.project(&mint).await;
+    assert!(project.dao_init);
+    assert_eq!(project.state, ProjectState::DaoLive);
+    assert_eq!(project.treasury, dao.native_treasury);
+    assert_eq!(env.token_balance(&dao.dao_token_account).await, DAO_ALLOCATION);
+
+    let (result, _) = env.init_dao(&mint, "Tatami DAO 2", 1).await;
+    assert_error(result, Errors::InvalidStateTransition);
+}
+
+#[tokio::test]
+async fn initialize_dao_respects_allocation_cap() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+
+    let (result, _) = env.init_dao(&mint, "Tatami DAO", DAO_ALLOCATION + 1).await;
+    assert_error(result, Errors::DaoAllocationExceeded);
+}
+
+#[tokio::test]
+async fn initialize_dao_rejects_invalid_quorum() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+
+    let mut args = dao_args("Tatami DAO", DAO_ALLOCATION);
+    args.quorum = 101;
+
+    let ix = instructions::initialize_dao(env.admin.pubkey(), mint, None, Pubkey::new_unique(), args);
+    assert_error(env.send(&[ix], &[]).await, Errors::InvalidQuorum);
+}
+
+#[tokio::test]
+async fn initialize_dao_requires_council_token_holding() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+    let council_mint = Keypair::new();
+    let governed_account = Pubkey::new_unique();
+
+    let dao = DaoAddresses::derive("Tatami DAO", &mint, Some(&council_mint.pubkey()), &governed_account);
+
+    let ix = Instruction {
+        program_id: tatami_v2::ID,
+        accounts: accounts::InitializeDao {
+            signer: env.admin.pubkey(),
+            config: pda::config().0,
+            project: pda::project(&mint).0,
+            mint,
+            council_mint: Some(council_mint.pubkey()),
+            realm_account: dao.realm,
+            community_token_holding: dao.community_token_holding,
+            council_token_holding: None,
+            realm_config: dao.realm_config,
+            governance: dao.governance,
+            governed_account,
+            native_treasury: dao.native_treasury,
+            dao_token_account: dao.dao_token_account,
+            realm_program: REALMS_ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            rent: sysvar::rent::ID
+        }.to_account_metas(None),
+        data: instruction::InitializeDao {
+            name: "Tatami DAO".to_string(),
+            supply: DAO_ALLOCATION,
+            min_vote_to_govern: 1_000,
+            is_council: true,
+            quorum: 60,
+            vote_duration: 86_400
+        }.data()
+    };
+
+    assert_error(env.send(&[ix], &[&council_mint]).await, Errors::NoCouncilTokenHolding);
+}
+
+#[tokio::test]
+async fn burn_authority_finalizes_project() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+
+    let ix = instructions::burn_authority(env.admin.pubkey(), mint);
+    assert_error(env.send(slice::from_ref(&ix), &[]).await, Errors::DaoNotInitialized);
+
+    let (result, _) = env.init_dao(&mint, "Tatami DAO", DAO_ALLOCATION).await;
+    result.unwrap();
+
+    env.send(slice::from_ref(&ix), &[]).await.unwrap();
+
+    assert_eq!(env.mint_state(&mint).await.mint_authority, COption::None);
+
+    let project = env.project(&mint).await;
+    assert!(!project.mint_exist);
+    assert_eq!(project.state, ProjectState::Finalized);
+
+    let (result, _) = env.init_dao(&mint, "Second DAO", 0).await;
+    assert_error(result, Errors::InvalidStateTransition);
+
+    assert_error(env.send(&[ix], &[]).await, Errors::InvalidStateTransition);
+}
+
+#[tokio::test]
+async fn finalized_project_rejects_new_distribution() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch(InitProjectArgs { dao_allocation: 0, ..launch_args() }).await.pubkey();
+    let contributor = Keypair::new();
+    env.fund(&contributor.pubkey(), 5 * LAMPORTS_PER_SOL).await;
+
+    let now = env.now().await;
+    env.send(&[
+        instructions::create_sale(env.admin.pubkey(), mint, sale_args(now, [0; 32])),
+        instructions::burn_authority(env.admin.pubkey(), mint)
+    ], &[]).await.unwrap();
+
+    let ix = instructions::airdrop_tokens(env.admin.pubkey(), mint, Pubkey::new_unique(), 1);
+    assert_error(env.send(&[ix], &[]).await, Errors::ProjectFinalized);
+
+    let ix = instructions::set_airdrop_deadline(env.admin.pubkey(), mint, now + 60);
+    assert_error(env.send(&[ix], &[]).await, Errors::ProjectFinalized);
+
+    let ix = instructions::create_auction(env.admin.pubkey(), mint, auction_args(now));
+    assert_error(env.send(&[ix], &[]).await, Errors::ProjectFinalized);
+
+    let ix = instructions::contribute(contributor.pubkey(), mint, LAMPORTS_PER_SOL, vec![]);
+    assert_error(env.send_as(&contributor, &[ix]).await, Errors::ProjectFinalized);
+}
+
+#[tokio::test]
+async fn set_airdrop_deadline_only_once() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+    let now = env.now().await;
+
+    let ix = instructions::set_airdrop_deadline(env.admin.pubkey(), mint, now - 1);
+    assert_error(env.send(&[ix], &[]).await, Errors::InvalidDeadline);
+
+    env.send(&[instructions::set_airdrop_deadline(env.admin.pubkey(), mint, now + 60)], &[]).await.unwrap();
+    assert_eq!(env.project(&mint).await.airdrop_deadline, now + 60);
+
+    let ix = instructions::set_airdrop_deadline(env.admin.pubkey(), mint, now + 120);
+    assert_error(env.send(&[ix], &[]).await, Errors::AirdropDeadlineSet);
+}
+
+#[tokio::test]
+async fn airdrop_rejected_after_deadline() {
+    let mut env = TestEnv::new().await;
+    let (mint, deadline) = launch_with_deadline(&mut env, ClawbackPolicy::Creator).await;
+
+    env.warp_to_timestamp(deadline + 1).await;
+
+    let ix = instructions::airdrop_tokens(env.admin.pubkey(), mint.pubkey(), Pubkey::new_unique(), 1);
+    assert_error(env.send(&[ix], &[]).await, Errors::AirdropEnded);
+}
+
+#[tokio::test]
+async fn close_airdrop_returns_tokens_to_creator() {
+    let mut env = TestEnv::new().await;
+    let (mint, deadline) = launch_with_deadline(&mut env, ClawbackPolicy::Creator).await;
+    let mint = mint.pubkey();
+
+    let project = env.project(&mint).await;
+    let ix = instructions::close_airdrop(env.admin.pubkey(), &project);
+    assert_error(env.send(slice::from_ref(&ix), &[]).await, Errors::AirdropNotEnded);
+
+    env.warp_to_timestamp(deadline + 1).await;
+
+    let missing = close_airdrop_ix(env.admin.pubkey(), &project, None, None);
+    assert_error(env.send(&[missing], &[]).await, Errors::MissingClawbackAccount);
+
+    env.send(slice::from_ref(&ix), &[]).await.unwrap();
+
+    assert_eq!(env.vault_balance(&mint).await, 0);
+    assert_eq!(env.token_balance(&ata(&env.admin.pubkey(), &mint)).await, TEAM_SUPPLY + AIRDROP_SUPPLY);
+
+    let project = env.project(&mint).await;
+    assert!(project.airdrop_closed);
+    assert_eq!(project.clawed_back, AIRDROP_SUPPLY);
+
+    assert_error(env.send(&[ix], &[]).await, Errors::AirdropClosed);
+
+    let ix = instructions::airdrop_tokens(env.admin.pubkey(), mint, Pubkey::new_unique(), 1);
+    assert_error(env.send(&[ix], &[]).await, Errors::AirdropClosed);
+}
+
+#[tokio::test]
+async fn close_airdrop_burns_tokens() {
+    let mut env = TestEnv::new().await;
+    let (mint, deadline) = launch_with_deadline(&mut env, ClawbackPolicy::Burn).await;
+    let mint = mint.pubkey();
+
+    env.warp_to_timestamp(deadline + 1).await;
+
+    let project = env.project(&mint).await;
+    env.send(&[instructions::close_airdrop(env.admin.pubkey(), &project)], &[]).await.unwrap();
+
+    assert_eq!(env.mint_state(&mint).await.supply, TEAM_SUPPLY);
+    assert_eq!(env.project(&mint).await.burned, AIRDROP_SUPPLY);
+}
+
+#[tokio::test]
+async fn close_airdrop_moves_tokens_to_treasury() {
+    let mut env = TestEnv::new().await;
+    let (mint, deadline) = launch_with_deadline(&mut env, ClawbackPolicy::Treasury).await;
+    let mint = mint.pubkey();
+
+    env.warp_to_timestamp(deadline + 1).await;
+
+    let project = env.project(&mint).await;
+    let ix = close_airdrop_ix(env.admin.pubkey(), &project, None, None);
+    assert_error(env.send(&[ix], &[]).await, Errors::DaoNotInitialized);
+
+    let (result, dao) = env.init_dao(&mint, "Tatami DAO", DAO_ALLOCATION).await;
+    result.unwrap();
+
+    let project = env.project(&mint).await;
+    env.send(&[instructions::close_airdrop(env.admin.pubkey(), &project)], &[]).await.unwrap();
+
+    assert_eq!(env.token_balance(&dao.dao_token_account).await, DAO_ALLOCATION + AIRDROP_SUPPLY);
+}
+
+#[tokio::test]
+async fn close_airdrop_requires_deadline() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+
+    let project = env.project(&mint).await;
+    let ix = instructions::close_airdrop(env.admin.pubkey(), &project);
+    assert_error(env.send(&[ix], &[]).await, Errors::NoAirdropDeadline);
+}
+
+#[tokio::test]
+async fn burn_vault_tokens_tracks_burned_supply() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+
+    env.send(&[instructions::burn_vault_tokens(env.admin.pubkey(), mint, 1_000)], &[]).await.unwrap();
+
+    assert_eq!(env.vault_balance(&mint).await, AIRDROP_SUPPLY - 1_000);
+    assert_eq!(env.project(&mint).await.burned, 1_000);
+
+    let ix = instructions::burn_vault_tokens(env.admin.pubkey(), mint, 0);
+    assert_error(env.send(&[ix], &[]).await, Errors::InvalidAmount);
+
+    let ix = instructions::burn_vault_tokens(env.admin.pubkey(), mint, AIRDROP_SUPPLY);
+    assert_error(env.send(&[ix], &[]).await, Errors::InsufficientVaultBalance);
+
+    let stranger = Keypair::new();
+    env.fund(&stranger.pubkey(), 1_000_000_000).await;
+
+    let ix = instructions::burn_vault_tokens(stranger.pubkey(), mint, 1);
+    assert_error(env.send_as(&stranger, &[ix]).await, Errors::Unauthorized);
+}
+
+#[tokio::test]
+async fn sweep_to_treasury_moves_vault_balance() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch(InitProjectArgs { recipients: 1, ..launch_args() }).await.pubkey();
+
+    let (result, dao) = env.init_dao(&mint, "Tatami DAO", DAO_ALLOCATION).await;
+    result.unwrap();
+
+    let ix = instructions::sweep_to_treasury(env.admin.pubkey(), mint, dao.native_treasury);
+    assert_error(env.send(slice::from_ref(&ix), &[]).await, Errors::AirdropNotEnded);
+
+    env.send(&[instructions::airdrop_tokens(env.admin.pubkey(), mint, Pubkey::new_unique(), 1_000)], &[]).await.unwrap();
+    env.send(slice::from_ref(&ix), &[]).await.unwrap();
+
+    assert_eq!(env.vault_balance(&mint).await, 0);
+    assert_eq!(env.token_balance(&dao.dao_token_account).await, DAO_ALLOCATION + AIRDROP_SUPPLY - 1_000);
+    assert_eq!(env.project(&mint).await.swept, AIRDROP_SUPPLY - 1_000);
+
+    assert_error(env.send(&[ix], &[]).await, Errors::VaultEmpty);
+}
+
+#[tokio::test]
+async fn sweep_to_treasury_waits_for_airdrop_deadline() {
+    let mut env = TestEnv::new().await;
+    let (mint, deadline) = launch_with_deadline(&mut env, ClawbackPolicy::Creator).await;
+    let mint = mint.pubkey();
+
+    let (result, dao) = env.init_dao(&mint, "Tatami DAO", DAO_ALLOCATION).await;
+    result.unwrap();
+
+    let ix = instructions::sweep_to_treasury(env.admin.pubkey(), mint, dao.native_treasury);
+    assert_error(env.send(slice::from_ref(&ix), &[]).await, Errors::AirdropNotEnded);
+
+    env.warp_to_timestamp(deadline + 1).await;
+    env.send(&[ix], &[]).await.unwrap();
+
+    assert_eq!(env.project(&mint).await.swept, AIRDROP_SUPPLY);
+}
+
+#[tokio::test]
+async fn close_project_reclaims_rent() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch(InitProjectArgs {
+        dao_allocation: 0,
+        ..launch_args()
+    }).await.pubkey();
+
+    let ix = instructions::close_project(env.admin.pubkey(), mint);
+    assert_error(env.send(slice::from_ref(&ix), &[]).await, Errors::VaultNotEmpty);
+
+    env.send(&[instructions::burn_vault_tokens(env.admin.pubkey(), mint, AIRDROP_SUPPLY)], &[]).await.unwrap();
+    assert_error(env.send(slice::from_ref(&ix), &[]).await, Errors::MintAuthorityNotBurned);
+
+    env.send(&[instructions::burn_authority(env.admin.pubkey(), mint)], &[]).await.unwrap();
+
+    let data = ProfileData {
+        website: "https://tatami.example".to_string(),
+        socials: Vec::new(),
+        description_hash: [0; 32],
+        category: ProjectCategory::Other,
+        tags: Vec::new()
+    };
+    env.send(&[instructions::set_project_profile(env.admin.pubkey(), mint, data)], &[]).await.unwrap();
+    assert_error(env.send(slice::from_ref(&ix), &[]).await, Errors::ProfileNotClosed);
+
+    env.send(&[instructions::close_project_profile(env.admin.pubkey(), mint)], &[]).await.unwrap();
+    env.send(&[ix], &[]).await.unwrap();
+
+    assert!(env.account(&pda::project(&mint).0).await.is_none());
+    assert!(env.account(&pda::vault_token_account(&mint)).await.is_none());
+}
+
+#[tokio::test]
+async fn finalized_project_with_planned_dao_closes() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+
+    env.send(&[instructions::burn_vault_tokens(env.admin.pubkey(), mint, AIRDROP_SUPPLY)], &[]).await.unwrap();
+
+    let burn = instructions::burn_authority(env.admin.pubkey(), mint);
+    assert_error(env.send(slice::from_ref(&burn), &[]).await, Errors::DaoNotInitialized);
+
+    let ix = instructions::close_project(env.admin.pubkey(), mint);
+    assert_error(env.send(slice::from_ref(&ix), &[]).await, Errors::MintAuthorityNotBurned);
+
+    let (result, _) = env.init_dao(&mint, "Tatami DAO", DAO_ALLOCATION).await;
+    result.unwrap();
+
+    env.send(&[burn], &[]).await.unwrap();
+    env.send(&[ix], &[]).await.unwrap();
+    assert!(env.account(&pda::project(&mint).0).await.is_none());
+}
+
+#[tokio::test]
+async fn migrate_legacy_project() {
+    let creator = Keypair::new();
+    let mint = Pubkey::new_unique();
+
+    let mut program_test = program_test();
+    add_legacy_project(&mut program_test, &creator.pubkey(), &mint);
+
+    let mut env = TestEnv::with(program_test, true).await;
+    env.fund(&creator.pubkey(), 1_000_000_000).await;
+
+    let ix = instructions::migrate_project(creator.pubkey(), mint);
+    env.send_as(&creator, slice::from_ref(&ix)).await.unwrap();
+
+    let project = env.project(&mint).await;
+    assert_eq!(project.version, PROJECT_VERSION);
+    assert_eq!(project.state, ProjectState::Distributed);
+    assert_eq!(project.recipients_paid, 3);
+    assert_eq!(project.dao_allocation, 0);
+    assert_eq!(project.features, 0);
+    assert_eq!(env.mint_state(&mint).await.mint_authority, COption::Some(pda::project(&mint).0));
+    assert_eq!(env.account(&pda::project(&mint).0).await.unwrap().data.len(), 8 + Project::INIT_SPACE);
+
+    assert_error(env.send_as(&creator, &[ix]).await, Errors::AlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrate_pads_shorter_project_layouts() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+    let address = pda::project(&mint).0;
+
+    // Version 1 ended at `lp_lock`, followed by 5 reserved bytes.
+    let mut account = env.account(&address).await.unwrap();
+    account.data.truncate(8 + Project::INIT_SPACE - 2 * 32 - 64 + 5);
+    account.data[8] = 1;
+    env.ctx.set_account(&address, &account.into());
+
+    let data = env.account(&address).await.unwrap().data;
+    assert!(matches!(decode_project(&data), Ok(ProjectAccount::Outdated(project)) if project.version == 1));
+
+    let ix = instructions::set_airdrop_deadline(env.admin.pubkey(), mint, env.now().await + 60);
+    assert!(env.send(slice::from_ref(&ix), &[]).await.is_err());
+
+    env.send(&[instructions::migrate_project(env.admin.pubkey(), mint)], &[]).await.unwrap();
+    env.send(&[ix], &[]).await.unwrap();
+
+    let project = env.project(&mint).await;
+    assert_eq!(project.version, PROJECT_VERSION);
+    assert_eq!(project.mint, mint);
+    assert_eq!(project.lp_mint, Pubkey::default());
+    assert_eq!(env.account(&address).await.unwrap().data.len(), 8 + Project::INIT_SPACE);
+}
+
+#[tokio::test]
+async fn migrate_rejects_unknown_layout() {
+    let creator = Keypair::new();
+    let mint = Pubkey::new_unique();
+
+    let mut program_test = program_test();
+    program_test.add_account(mint, mint_account(&creator.pubkey()));
+    program_test.add_account(pda::project(&mint).0, program_account([0; 8], [1u8; 16]));
+
+    let mut env = TestEnv::with(program_test, true).await;
+    env.fund(&creator.pubkey(), 1_000_000_000).await;
+
+    let ix = instructions::migrate_project(creator.pubkey(), mint);
+    assert_error(env.send_as(&creator, &[ix]).await, Errors::InvalidAccountVersion);
+}
+
+#[tokio::test]
+async fn migrate_legacy_config() {
+    let admin = Keypair::new();
+
+    let mut program_test = program_test();
+    add_legacy_config(&mut program_test, &admin.pubkey());
+
+    let mut env = TestEnv::with(program_test, false).await;
+    env.fund(&admin.pubkey(), 1_000_000_000).await;
+
+    let ix = instructions::migrate_config(env.admin.pubkey());
+    assert_error(env.send(&[ix], &[]).await, Errors::Unauthorized);
+
+    env.send_as(&admin, &[instructions::migrate_config(admin.pubkey())]).await.unwrap();
+
+    let config = env.config().await;
+    assert_eq!(config.version, CONFIG_VERSION);
+    assert_eq!(config.fee, FEE);
+    assert_eq!(config.creator, admin.pubkey());
+    assert_eq!(env.account(&pda::config().0).await.unwrap().data.len(), 8 + Config::INIT_SPACE);
+}
+
+fn schedule(dao_fee: u64) -> FeeSchedule {
+    FeeSchedule {
+        dao_fee,
+        lp_fee: 300,
+        vesting_fee: 700,
+        holder_discount_mint: Pubkey::default(),
+        holder_discount_min_balance: 0,
+        holder_discount_bps: 0
+    }
+}
+
+#[tokio::test]
+async fn launch_fee_includes_paid_features() {
+    let mut env = TestEnv::new().await;
+    env.send(&[instructions::set_fee_schedule(env.admin.pubkey(), schedule(500))], &[]).await.unwrap();
+
+    let before = env.vault_lamports().await;
+    let mint = env.launch(InitProjectArgs { features: FEATURE_DAO | FEATURE_LP, ..launch_args() }).await.pubkey();
+
+    assert_eq!(env.vault_lamports().await, before + FEE + 500 + 300);
+    assert_eq!(env.project(&mint).await.fee_paid, FEE + 800);
+
+    let mint = env.launch(InitProjectArgs { features: 0, dao_allocation: 0, ..launch_args() }).await.pubkey();
+    assert_eq!(env.project(&mint).await.fee_paid, FEE);
+
+    let (result, _) = env.init_dao(&mint, "Tatami DAO", 0).await;
+    assert_error(result, Errors::FeatureNotPaid);
+
+    let (result, _) = env.try_launch(InitProjectArgs { features: 0, ..launch_args() }, LaunchOptions::default()).await;
+    assert_error(result, Errors::FeatureNotPaid);
+}
+
+#[tokio::test]
+async fn fee_override_waives_or_discounts() {
+    let mut env = TestEnv::new().await;
+    let creator = env.admin.pubkey();
+    let options = || LaunchOptions { fee_override: true, ..LaunchOptions::default() };
+
+    let ix = instructions::set_fee_override(env.admin.pubkey(), creator, 10_001, false);
+    assert_error(env.send(&[ix], &[]).await, Errors::InvalidBasisPoints);
+
+    env.send(&[instructions::set_fee_override(env.admin.pubkey(), creator, 0, true)], &[]).await.unwrap();
+    let (result, mint) = env.try_launch(launch_args(), options()).await;
+    result.unwrap();
+    assert_eq!(env.project(&mint.pubkey()).await.fee_paid, 0);
+
+    env.send(&[instructions::set_fee_override(env.admin.pubkey(), creator, 5_000, false)], &[]).await.unwrap();
+    let (result, mint) = env.try_launch(launch_args(), options()).await;
+    result.unwrap();
+    assert_eq!(env.project(&mint.pubkey()).await.fee_paid, FEE / 2);
+
+    env.send(&[instructions::remove_fee_override(env.admin.pubkey(), creator)], &[]).await.unwrap();
+    assert!(env.account(&pda::fee_override(&creator).0).await.is_none());
+}
+
+#[tokio::test]
+async fn holders_get_a_discount() {
+    let mut env = TestEnv::new().await;
+    let held_mint = env.launch_default().await.pubkey();
+    let held_account = ata(&env.admin.pubkey(), &held_mint);
+
+    let mut fees = schedule(0);
+    fees.holder_discount_mint = held_mint;
+    fees.holder_discount_min_balance = TEAM_SUPPLY;
+    fees.holder_discount_bps = 2_000;
+    env.send(&[instructions::set_fee_schedule(env.admin.pubkey(), fees.clone())], &[]).await.unwrap();
+
+    let options = LaunchOptions { discount_token_account: Some(held_account), ..LaunchOptions::default() };
+    let (result, mint) = env.try_launch(launch_args(), options).await;
+    result.unwrap();
+    assert_eq!(env.project(&mint.pubkey()).await.fee_paid, FEE * 8 / 10);
+
+    fees.holder_discount_min_balance = TEAM_SUPPLY + 1;
+    env.send(&[instructions::set_fee_schedule(env.admin.pubkey(), fees)], &[]).await.unwrap();
+
+    let options = LaunchOptions { discount_token_account: Some(held_account), ..LaunchOptions::default() };
+    let (result, _) = env.try_launch(launch_args(), options).await;
+    assert_error(result, Errors::DiscountNotEligible);
+}
+
+#[tokio::test]
+async fn launch_fee_overflow_is_rejected() {
+    let mut env = TestEnv::new().await;
+    env.send(&[
+        instructions::update_config(env.admin.pubkey(), u64::MAX),
+        instructions::set_fee_schedule(env.admin.pubkey(), schedule(1))
+    ], &[]).await.unwrap();
+
+    let (result, _) = env.try_launch(launch_args(), LaunchOptions::default()).await;
+    assert_error(result, Errors::FeeOverflow);
+}
+
+/// Points every account meta at `from` to `to` instead.
+fn replace_account(mut ix: Instruction, from: &Pubkey, to: &Pubkey) -> Instruction {
+    for meta in ix.accounts.iter_mut().filter(|meta| meta.pubkey == *from) {
+        meta.pubkey = *to;
+    }
+    ix
+}
+
+#[tokio::test]
+async fn launch_fee_paid_in_tokens() {
+    let mut env = TestEnv::new().await;
+    let fee_token = env.launch_default().await.pubkey();
+    let admin_fee_account = ata(&env.admin.pubkey(), &fee_token);
+
+    env.send(&[instructions::add_fee_mint(env.admin.pubkey(), fee_token, 500_000_000)], &[]).await.unwrap();
+
+    let lamports = env.vault_lamports().await;
+    let options = || LaunchOptions { fee_mint: Some(fee_token), ..LaunchOptions::default() };
+
+    let (result, mint) = env.try_launch(launch_args(), options()).await;
+    result.unwrap();
+
+    let project = env.project(&mint.pubkey()).await;
+    assert_eq!(project.fee_paid, FEE / 2);
+    assert_eq!(project.fee_mint, fee_token);
+    assert_eq!(env.token_balance(&pda::fee_vault(&fee_token)).await, FEE / 2);
+    assert_eq!(env.vault_lamports().await, lamports);
+
+    let ix = instructions::withdraw_token_fees(env.admin.pubkey(), fee_token, admin_fee_account, FEE / 2);
+    env.send(&[ix], &[]).await.unwrap();
+    assert_eq!(env.token_balance(&pda::fee_vault(&fee_token)).await, 0);
+    assert_eq!(env.token_balance(&admin_fee_account).await, TEAM_SUPPLY);
+
+    let mint = Keypair::new();
+    let ix = instructions::init_project_with(env.admin.pubkey(), mint.pubkey(), None, launch_args(), options());
+
+    let wrong_vault = replace_account(ix.clone(), &pda::fee_vault(&fee_token), &admin_fee_account);
+    assert_error(env.send(&[wrong_vault], &[&mint]).await, Errors::InvalidFeeAccount);
+
+    let missing_vault = replace_account(ix, &pda::fee_vault(&fee_token), &tatami_v2::ID);
+    assert_error(env.send(&[missing_vault], &[&mint]).await, Errors::MissingFeeAccount);
+
+    env.send(&[instructions::update_fee_mint(env.admin.pubkey(), fee_token, 500_000_000, false)], &[]).await.unwrap();
+    let (result, _) = env.try_launch(launch_args(), options()).await;
+    assert_error(result, Errors::FeeMintDisabled);
+}
+
+#[tokio::test]
+async fn admin_withdraws_sol_fees() {
+    let mut env = TestEnv::new().await;
+    env.launch_default().await;
+
+    let destination = Pubkey::new_unique();
+    env.send(&[instructions::withdraw_fees(env.admin.pubkey(), destination, FEE)], &[]).await.unwrap();
+    assert_eq!(env.account(&destination).await.unwrap().lamports, FEE);
+
+    let stranger = Keypair::new();
+    env.fund(&stranger.pubkey(), 1_000_000_000).await;
+    let ix = instructions::withdraw_fees(stranger.pubkey(), stranger.pubkey(), 1);
+    assert!(env.send_as(&stranger, &[ix]).await.is_err());
+}
+
+#[tokio::test]
+async fn referral_share_is_paid_to_partner() {
+    let mut env = TestEnv::new().await;
+    let partner = Pubkey::new_unique();
+    env.fund(&partner, 1_000_000_000).await;
+
+    let ix = instructions::set_referral(env.admin.pubkey(), partner, 10_001);
+    assert_error(env.send(&[ix], &[]).await, Errors::InvalidBasisPoints);
+
+    env.send(&[instructions::set_referral(env.admin.pubkey(), partner, 1_000)], &[]).await.unwrap();
+
+    let lamports = env.vault_lamports().await;
+    let options = || LaunchOptions { referrer: Some(partner), ..LaunchOptions::default() };
+
+    for _ in 0..2 {
+        let (result, mint) = env.try_launch(launch_args(), options()).await;
+        result.unwrap();
+        assert_eq!(env.project(&mint.pubkey()).await.referrer, partner);
+    }
+
+    let referral = env.referral(&partner).await;
+    assert_eq!(referral.projects_referred, 2);
+    assert_eq!(referral.lamports_earned, FEE / 5);
+    assert_eq!(env.account(&partner).await.unwrap().lamports, 1_000_000_000 + FEE / 5);
+    assert_eq!(env.vault_lamports().await, lamports + FEE * 9 / 5);
+
+    let mint = Keypair::new();
+    let ix = instructions::init_project_with(env.admin.pubkey(), mint.pubkey(), None, launch_args(), options());
+    let missing_referrer = replace_account(ix, &partner, &tatami_v2::ID);
+    assert_error(env.send(&[missing_referrer], &[&mint]).await, Errors::MissingReferrerAccount);
+
+    env.send(&[instructions::set_referral(env.admin.pubkey(), env.admin.pubkey(), 1_000)], &[]).await.unwrap();
+    let options = LaunchOptions { referrer: Some(env.admin.pubkey()), ..LaunchOptions::default() };
+    let (result, _) = env.try_launch(launch_args(), options).await;
+    assert_error(result, Errors::InvalidReferrer);
+
+    env.send(&[instructions::remove_referral(env.admin.pubkey(), partner)], &[]).await.unwrap();
+    assert!(env.account(&pda::referral(&partner).0).await.is_none());
+}
+
+#[tokio::test]
+async fn token_referral_shares_are_totalled_per_mint() {
+    let mut env = TestEnv::new().await;
+    let fee_token = env.launch_default().await.pubkey();
+    let partner = Pubkey::new_unique();
+
+    env.send(&[instructions::add_fee_mint(env.admin.pubkey(), fee_token, 500_000_000)], &[]).await.unwrap();
+    env.send(&[instructions::set_referral(env.admin.pubkey(), partner, 1_000)], &[]).await.unwrap();
+    env.send(&[instructions::airdrop_tokens(env.admin.pubkey(), fee_token, partner, 1)], &[]).await.unwrap();
+    env.send(&[instructions::init_referral_earnings(env.admin.pubkey(), partner, fee_token)], &[]).await.unwrap();
+
+    let options = || LaunchOptions { fee_mint: Some(fee_token), referrer: Some(partner), ..LaunchOptions::default() };
+
+    for _ in 0..2 {
+        let (result, _) = env.try_launch(launch_args(), options()).await;
+        result.unwrap();
+    }
+
+    let referral = env.referral(&partner).await;
+    assert_eq!(referral.projects_referred, 2);
+    assert_eq!(referral.lamports_earned, 0);
+    assert_eq!(env.referral_earnings(&partner, &fee_token).await.amount, FEE / 10);
+    assert_eq!(env.token_balance(&ata(&partner, &fee_token)).await, 1 + FEE / 10);
+
+    let mint = Keypair::new();
+    let ix = instructions::init_project_with(env.admin.pubkey(), mint.pubkey(), None, launch_args(), options());
+    let missing_earnings = replace_account(ix, &pda::referral_earnings(&partner, &fee_token).0, &tatami_v2::ID);
+    assert_error(env.send(&[missing_earnings], &[&mint]).await, Errors::MissingReferrerAccount);
+}
+
+#[tokio::test]
+async fn pause_stops_launches_and_airdrops() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch(InitProjectArgs { dao_allocation: 0, ..launch_args() }).await.pubkey();
+
+    env.send(&[instructions::set_pause(env.admin.pubkey(), true, 0)], &[]).await.unwrap();
+
+    let (result, _) = env.try_launch(launch_args(), LaunchOptions::default()).await;
+    assert_error(result, Errors::Paused);
+
+    let ix = instructions::airdrop_tokens(env.admin.pubkey(), mint, Pubkey::new_unique(), 1);
+    assert_error(env.send(&[ix], &[]).await, Errors::Paused);
+
+    let (result, _) = env.init_dao(&mint, "Tatami DAO", 0).await;
+    assert_error(result, Errors::Paused);
+
+    assert_error(env.send(&[initialize_lp_ix(env.admin.pubkey(), mint, vec![])], &[]).await, Errors::Paused);
+
+    env.send(&[instructions::burn_authority(env.admin.pubkey(), mint)], &[]).await.unwrap();
+
+    env.send(&[instructions::set_pause(env.admin.pubkey(), false, PAUSE_AIRDROP | PAUSE_INIT_LP)], &[]).await.unwrap();
+    assert!(!env.config().await.paused);
+
+    let ix = instructions::airdrop_tokens(env.admin.pubkey(), mint, Pubkey::new_unique(), 1);
+    assert_error(env.send(&[ix], &[]).await, Errors::Paused);
+    assert_error(env.send(&[initialize_lp_ix(env.admin.pubkey(), mint, vec![])], &[]).await, Errors::Paused);
+
+    env.launch_default().await;
+
+    let stranger = Keypair::new();
+    env.fund(&stranger.pubkey(), 1_000_000_000).await;
+    let ix = instructions::set_pause(stranger.pubkey(), false, 0);
+    assert!(env.send_as(&stranger, &[ix]).await.is_err());
+}
+
+#[tokio::test]
+async fn allowlist_requires_a_live_permit() {
+    let mut env = TestEnv::new().await;
+    let creator = env.admin.pubkey();
+    let options = || LaunchOptions { creator_permit: true, ..LaunchOptions::default() };
+
+    env.send(&[instructions::set_allowlist(env.admin.pubkey(), true, Pubkey::default())], &[]).await.unwrap();
+
+    let (result, _) = env.try_launch(launch_args(), LaunchOptions::default()).await;
+    assert_error(result, Errors::CreatorNotAllowed);
+
+    let expires_at = env.now().await + 3_600;
+    env.send(&[instructions::grant_creator_permit(env.admin.pubkey(), creator, expires_at)], &[]).await.unwrap();
+
+    let (result, _) = env.try_launch(launch_args(), options()).await;
+    result.unwrap();
+
+    env.warp_to_timestamp(expires_at).await;
+    let (result, _) = env.try_launch(launch_args(), options()).await;
+    assert_error(result, Errors::PermitExpired);
+
+    env.send(&[instructions::grant_creator_permit(env.admin.pubkey(), creator, 0)], &[]).await.unwrap();
+    env.send(&[instructions::revoke_creator_permit(env.admin.pubkey(), creator)], &[]).await.unwrap();
+
+    let permit = env.creator_permit(&creator).await;
+    assert!(permit.revoked);
+    assert_eq!(permit.nonce, 1);
+
+    let (result, _) = env.try_launch(launch_args(), options()).await;
+    assert_error(result, Errors::CreatorNotAllowed);
+
+    env.send(&[instructions::set_allowlist(env.admin.pubkey(), false, Pubkey::default())], &[]).await.unwrap();
+    env.launch_default().await;
+}
+
+#[tokio::test]
+async fn verifier_attestation_grants_a_permit() {
+    let mut env = TestEnv::new().await;
+    let verifier = Keypair::new();
+    let creator = Keypair::new();
+    env.fund(&creator.pubkey(), 1_000_000_000).await;
+
+    env.send(&[instructions::set_allowlist(env.admin.pubkey(), true, verifier.pubkey())], &[]).await.unwrap();
+
+    let message = permit_message(&creator.pubkey(), 0, 0);
+    let signature: [u8; 64] = verifier.sign_message(&message).as_ref().try_into().unwrap();
+    let attestation = instructions::verify_signature(&verifier.pubkey(), &signature, &message);
+
+    let claim = instructions::claim_creator_permit(creator.pubkey(), 0);
+    assert_error(env.send_as(&creator, slice::from_ref(&claim)).await, Errors::InvalidAttestation);
+
+    let forged: [u8; 64] = creator.sign_message(&message).as_ref().try_into().unwrap();
+    let forged = instructions::verify_signature(&creator.pubkey(), &forged, &message);
+    assert_error(env.send_as(&creator, &[forged, claim.clone()]).await, Errors::InvalidAttestation);
+
+    env.send_as(&creator, &[attestation.clone(), claim.clone()]).await.unwrap();
+
+    let permit = env.creator_permit(&creator.pubkey()).await;
+    assert_eq!(permit.issuer, verifier.pubkey());
+    assert_eq!(permit.expires_at, 0);
+
+    let ix = instructions::grant_creator_permit(creator.pubkey(), creator.pubkey(), 0);
+    assert_error(env.send_as(&creator, &[ix]).await, Errors::Unauthorized);
+
+    env.fund(&verifier.pubkey(), 1_000_000_000).await;
+    let ix = instructions::revoke_creator_permit(verifier.pubkey(), creator.pubkey());
+    env.send_as(&verifier, &[ix]).await.unwrap();
+
+    assert_error(env.send_as(&creator, &[attestation, claim.clone()]).await, Errors::InvalidAttestation);
+
+    let message = permit_message(&creator.pubkey(), 1, 0);
+    let signature: [u8; 64] = verifier.sign_message(&message).as_ref().try_into().unwrap();
+    let attestation = instructions::verify_signature(&verifier.pubkey(), &signature, &message);
+    env.send_as(&creator, &[attestation, claim]).await.unwrap();
+    assert!(!env.creator_permit(&creator.pubkey()).await.revoked);
+}
+
+#[tokio::test]
+async fn admin_set_needs_approved_proposals() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.pubkey();
+    let (second, third) = (Keypair::new(), Keypair::new());
+    env.fund(&second.pubkey(), 1_000_000_000).await;
+    env.fund(&third.pubkey(), 1_000_000_000).await;
+
+    let admins = vec![admin, second.pubkey(), third.pubkey()];
+    let ix = instructions::set_admins(admin, admins.clone(), 4);
+    assert_error(env.send(&[ix], &[]).await, Errors::InvalidAdminSet);
+    env.send(&[instructions::set_admins(admin, admins, 2)], &[]).await.unwrap();
+
+    assert_error(env.send(&[instructions::update_config(admin, 5)], &[]).await, Errors::ProposalRequired);
+
+    let action = AdminAction::UpdateConfig { fee: 5 };
+    let id = env.config().await.proposal_count;
+    env.send(&[instructions::create_proposal(admin, id, action)], &[]).await.unwrap();
+
+    let execute = |fee| instructions::with_proposal(instructions::update_config(admin, fee), id);
+    assert_error(env.send(&[execute(5)], &[]).await, Errors::ProposalNotApproved);
+
+    env.send_as(&second, &[instructions::approve_proposal(second.pubkey(), id)]).await.unwrap();
+    assert_error(env.send(&[execute(6)], &[]).await, Errors::ProposalMismatch);
+
+    env.send(&[execute(5)], &[]).await.unwrap();
+    assert_eq!(env.config().await.fee, 5);
+    assert_error(env.send(&[execute(5)], &[]).await, Errors::ProposalExecuted);
+
+    let ix = instructions::close_proposal(second.pubkey(), id);
+    assert_error(env.send_as(&second, &[ix]).await, Errors::Unauthorized);
+    env.send(&[instructions::close_proposal(admin, id)], &[]).await.unwrap();
+
+    // any single admin may pause, but lifting it needs a proposal
+    env.send_as(&third, &[instructions::set_pause(third.pubkey(), true, 0)]).await.unwrap();
+    assert!(env.config().await.paused);
+    let ix = instructions::set_pause(third.pubkey(), false, 0);
+    assert_error(env.send_as(&third, &[ix]).await, Errors::ProposalRequired);
+
+    let stranger = Keypair::new();
+    env.fund(&stranger.pubkey(), 1_000_000_000).await;
+    let ix = instructions::create_proposal(stranger.pubkey(), id + 1, AdminAction::UpdateConfig { fee: 0 });
+    assert_error(env.send_as(&stranger, &[ix]).await, Errors::Unauthorized);
+}
+
+#[tokio::test]
+async fn admin_set_change_makes_proposals_stale() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.pubkey();
+    let second = Keypair::new();
+
+    env.send(&[instructions::set_admins(admin, vec![admin, second.pubkey()], 1)], &[]).await.unwrap();
+
+    let id = env.config().await.proposal_count;
+    env.send(&[instructions::create_proposal(admin, id, AdminAction::UpdateConfig { fee: 5 })], &[]).await.unwrap();
+
+    let action = AdminAction::SetAdmins { admins: vec![admin], threshold: 1 };
+    env.send(&[instructions::create_proposal(admin, id + 1, action)], &[]).await.unwrap();
+    env.send(&[instructions::with_proposal(instructions::set_admins(admin, vec![admin], 1), id + 1)], &[]).await.unwrap();
+
+    let ix = instructions::with_proposal(instructions::update_config(admin, 5), id);
+    assert_error(env.send(&[ix], &[]).await, Errors::ProposalStale);
+    assert_error(env.send(&[instructions::approve_proposal(admin, id)], &[]).await, Errors::ProposalStale);
+}
+
+#[tokio::test]
+async fn ownership_handover_takes_two_steps() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch(InitProjectArgs { dao_allocation: 0, ..launch_args() }).await.pubkey();
+    let admin = env.admin.pubkey();
+    let next = Keypair::new();
+    env.fund(&next.pubkey(), 1_000_000_000).await;
+
+    let accept = instructions::accept_project_ownership(next.pubkey(), mint);
+    assert_error(env.send_as(&next, slice::from_ref(&accept)).await, Errors::Unauthorized);
+
+    let ix = instructions::transfer_project_ownership(admin, mint, admin);
+    assert_error(env.send(&[ix], &[]).await, Errors::InvalidNewCreator);
+
+    let operator = Keypair::new();
+    env.fund(&operator.pubkey(), 1_000_000_000).await;
+    env.send(&[instructions::set_project_role(admin, mint, operator.pubkey(), ROLE_AIRDROP)], &[]).await.unwrap();
+
+    env.send(&[instructions::transfer_project_ownership(admin, mint, Pubkey::new_unique())], &[]).await.unwrap();
+    assert_eq!(env.metadata(&mint).await.update_authority, pda::project(&mint).0);
+
+    env.send(&[instructions::transfer_project_ownership(admin, mint, Pubkey::default())], &[]).await.unwrap();
+    assert_eq!(env.metadata(&mint).await.update_authority, admin);
+
+    env.send(&[instructions::transfer_project_ownership(admin, mint, next.pubkey())], &[]).await.unwrap();
+    assert_eq!(env.project(&mint).await.pending_creator, next.pubkey());
+    assert_eq!(env.mint_state(&mint).await.mint_authority, COption::Some(pda::project(&mint).0));
+
+    env.send_as(&next, &[accept]).await.unwrap();
+
+    let project = env.project(&mint).await;
+    assert_eq!(project.creator, next.pubkey());
+    assert_eq!(project.pending_creator, Pubkey::default());
+    assert_eq!(env.metadata(&mint).await.update_authority, next.pubkey());
+
+    let ix = instructions::airdrop_tokens(admin, mint, Pubkey::new_unique(), 1);
+    assert_error(env.send(&[ix], &[]).await, Errors::Unauthorized);
+
+    let ix = instructions::airdrop_tokens_as_operator(operator.pubkey(), mint, Pubkey::new_unique(), 1);
+    assert_error(env.send_as(&operator, slice::from_ref(&ix)).await, Errors::Unauthorized);
+
+    let grant = instructions::set_project_role(next.pubkey(), mint, operator.pubkey(), ROLE_AIRDROP);
+    env.send_as(&next, &[grant]).await.unwrap();
+    env.send_as(&operator, &[ix]).await.unwrap();
+
+    env.send_as(&next, &[instructions::burn_authority(next.pubkey(), mint)]).await.unwrap();
+    assert_eq!(env.mint_state(&mint).await.mint_authority, COption::None);
+}
+
+#[tokio::test]
+async fn airdrop_operator_can_only_distribute() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+    let admin = env.admin.pubkey();
+    let operator = Keypair::new();
+    let receiver = Pubkey::new_unique();
+    env.fund(&operator.pubkey(), 1_000_000_000).await;
+
+    env.send(&[instructions::set_project_role(admin, mint, operator.pubkey(), ROLE_AIRDROP)], &[]).await.unwrap();
+
+    let ix = instructions::airdrop_tokens_as_operator(operator.pubkey(), mint, receiver, 1_000);
+    env.send_as(&operator, &[ix]).await.unwrap();
+    assert_eq!(env.token_balance(&ata(&receiver, &mint)).await, 1_000);
+
+    let ix = instructions::burn_authority(operator.pubkey(), mint);
+    assert!(env.send_as(&operator, &[ix]).await.is_err());
+
+    env.send(&[instructions::set_project_role(admin, mint, operator.pubkey(), 0)], &[]).await.unwrap();
+    let ix = instructions::airdrop_tokens_as_operator(operator.pubkey(), mint, receiver, 1_000);
+    assert_error(env.send_as(&operator, &[ix]).await, Errors::Unauthorized);
+
+    env.send(&[instructions::remove_project_role(admin, mint, operator.pubkey())], &[]).await.unwrap();
+    let project = pda::project(&mint).0;
+    assert!(env.account(&pda::project_role(&project, &operator.pubkey()).0).await.is_none());
+}
+
+#[tokio::test]
+async fn co_creators_verify_themselves() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.pubkey();
+    let co_creator = Keypair::new();
+    env.fund(&co_creator.pubkey(), 1_000_000_000).await;
+
+    let creators = |shares: [u8; 2]| vec![
+        CreatorShare { address: admin, share: shares[0] },
+        CreatorShare { address: co_creator.pubkey(), share: shares[1] }
+    ];
+
+    let args = InitProjectArgs { creators: creators([60, 30]), ..launch_args() };
+    let (result, _) = env.try_launch(args, LaunchOptions::default()).await;
+    assert_error(result, Errors::InvalidCreators);
+
+    let collection = Pubkey::new_unique();
+    let mint = env.launch(InitProjectArgs {
+        creators: creators([60, 40]),
+        collection: Some(collection),
+        ..launch_args()
+    }).await.pubkey();
+
+    let verified = |metadata: Metadata| -> Vec<bool> {
+        metadata.creators.unwrap().iter().map(|creator| creator.verified).collect()
+    };
+
+    let metadata = env.metadata(&mint).await;
+    assert_eq!(metadata.collection.as_ref().map(|collection| collection.key), Some(collection));
+    assert_eq!(verified(metadata), [true, false]);
+
+    let stranger = Keypair::new();
+    env.fund(&stranger.pubkey(), 1_000_000_000).await;
+    assert!(env.send_as(&stranger, &[instructions::verify_creator(stranger.pubkey(), mint)]).await.is_err());
+
+    env.send_as(&co_creator, &[instructions::verify_creator(co_creator.pubkey(), mint)]).await.unwrap();
+    assert_eq!(verified(env.metadata(&mint).await), [true, true]);
+}
+
+#[tokio::test]
+async fn registry_indexes_launches_in_order() {
+    let creator = Keypair::new();
+    let legacy_mint = Pubkey::new_unique();
+
+    let mut program_test = program_test();
+    add_legacy_project(&mut program_test, &creator.pubkey(), &legacy_mint);
+
+    let mut env = TestEnv::with(program_test, true).await;
+    env.fund(&creator.pubkey(), 1_000_000_000).await;
+
+    let first = env.launch_default().await.pubkey();
+    let second = env.launch_default().await.pubkey();
+
+    assert!(!env.project(&first).await.registered);
+    assert_eq!(env.config().await.project_count, 0);
+
+    env.register(&first).await.unwrap();
+    env.register(&second).await.unwrap();
+
+    assert_eq!(env.config().await.project_count, 2);
+    assert_eq!(env.project(&second).await.id, 1);
+
+    let index = env.project_index(0).await;
+    assert_eq!(index.project, pda::project(&first).0);
+    assert_eq!(index.mint, first);
+    assert_eq!(env.project_index(1).await.mint, second);
+    assert_eq!(env.creator_registry(&env.admin.pubkey()).await.projects, [0, 1]);
+
+    env.send_as(&creator, &[instructions::migrate_project(creator.pubkey(), legacy_mint)]).await.unwrap();
+    let legacy = env.project(&legacy_mint).await;
+    assert!(!legacy.registered);
+
+    env.register(&legacy_mint).await.unwrap();
+    let legacy = env.project(&legacy_mint).await;
+    assert!(legacy.registered);
+    assert_eq!(legacy.id, 2);
+    assert_eq!(env.project_index(2).await.mint, legacy_mint);
+    assert_eq!(env.creator_registry(&creator.pubkey()).await.projects, [2]);
+
+    assert_error(env.register(&legacy_mint).await, Errors::AlreadyRegistered);
+}
+
+#[tokio::test]
+async fn registry_pages_a_full_creator_list() {
+    let mut env = TestEnv::new().await;
+    let creator = env.admin.pubkey();
+    let mint = env.launch_default().await.pubkey();
+    let id = env.next_project_id().await;
+    let ids = |len| (100..100 + len as u64).collect::<Vec<_>>();
+
+    env.set_creator_registry(&creator, 0, ids(MAX_CREATOR_PROJECTS - 1));
+    let ix = instructions::register_project(creator, mint, creator, id, 1);
+    assert_error(env.send(&[ix], &[]).await, Errors::InvalidRegistryPage);
+
+    env.set_creator_registry(&creator, 0, ids(MAX_CREATOR_PROJECTS));
+    let ix = instructions::register_project(creator, mint, creator, id, 0);
+    assert_error(env.send(&[ix], &[]).await, Errors::CreatorRegistryFull);
+
+    env.register(&mint).await.unwrap();
+    assert_eq!(env.project(&mint).await.id, id);
+    assert_eq!(env.creator_registry_page(&creator, 1).await.unwrap().projects, [id]);
+}
+
+#[tokio::test]
+async fn project_profile_enforces_limits() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+
+    let data = ProfileData {
+        website: "https://tatami.example".to_string(),
+        socials: vec!["https://x.com/tatami".to_string()],
+        description_hash: [7; 32],
+        category: ProjectCategory::Gaming,
+        tags: vec!["rpg".to_string(), "fair-launch".to_string()]
+    };
+
+    env.send(&[instructions::set_project_profile(env.admin.pubkey(), mint, data.clone())], &[]).await.unwrap();
+    let profile = env.project_profile(&mint).await.unwrap();
+    assert_eq!(profile.project, pda::project(&mint).0);
+    assert_eq!(profile.website, data.website);
+    assert_eq!(profile.category, ProjectCategory::Gaming);
+    assert_eq!(profile.tags, data.tags);
+
+    let long_tag = ProfileData { tags: vec!["x".repeat(17)], ..data.clone() };
+    let ix = instructions::set_project_profile(env.admin.pubkey(), mint, long_tag);
+    assert_error(env.send(&[ix], &[]).await, Errors::InvalidProfileField);
+
+    let too_many = ProfileData { socials: vec![String::new(); 5], ..data.clone() };
+    let ix = instructions::set_project_profile(env.admin.pubkey(), mint, too_many);
+    assert_error(env.send(&[ix], &[]).await, Errors::TooManyProfileEntries);
+
+    let stranger = Keypair::new();
+    env.fund(&stranger.pubkey(), 1_000_000_000).await;
+    let ix = instructions::set_project_profile(stranger.pubkey(), mint, data);
+    assert!(env.send_as(&stranger, &[ix]).await.is_err());
+
+    env.send(&[instructions::close_project_profile(env.admin.pubkey(), mint)], &[]).await.unwrap();
+    assert!(env.project_profile(&mint).await.is_none());
+}
+
+#[tokio::test]
+async fn presale_splits_tokens_pro_rata() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+
+    let (early, late, stranger) = (Keypair::new(), Keypair::new(), Keypair::new());
+    for wallet in [&early, &late, &stranger] {
+        env.fund(&wallet.pubkey(), 5 * LAMPORTS_PER_SOL).await;
+    }
+
+    let tree = AllowlistTree::new(&[early.pubkey(), late.pubkey()]);
+    let start = env.now().await;
+    let args = sale_args(start, tree.root());
+    env.send(&[instructions::create_sale(env.admin.pubkey(), mint, args.clone())], &[]).await.unwrap();
+    assert_eq!(env.project(&mint).await.reserved_tokens, args.token_amount);
+
+    let ix = instructions::burn_vault_tokens(env.admin.pubkey(), mint, AIRDROP_SUPPLY);
+    assert_error(env.send(&[ix], &[]).await, Errors::InsufficientVaultBalance);
+
+    let contribute = |wallet: &Keypair, amount: u64| {
+        instructions::contribute(wallet.pubkey(), mint, amount, tree.proof(&wallet.pubkey()).unwrap_or_default())
+    };
+
+    assert_error(env.send_as(&stranger, &[contribute(&stranger, LAMPORTS_PER_SOL)]).await, Errors::NotOnAllowlist);
+
+    env.send_as(&early, &[contribute(&early, LAMPORTS_PER_SOL)]).await.unwrap();
+    env.send_as(&late, &[contribute(&late, LAMPORTS_PER_SOL / 2)]).await.unwrap();
+
+    let over_max = contribute(&early, 3 * LAMPORTS_PER_SOL / 2);
+    assert_error(env.send_as(&early, &[over_max]).await, Errors::InvalidContribution);
+
+    let over_cap = contribute(&late, 2 * LAMPORTS_PER_SOL);
+    assert_error(env.send_as(&late, &[over_cap]).await, Errors::SaleHardCapReached);
+
+    let claim = instructions::claim_sale_tokens(early.pubkey(), mint);
+    assert_error(env.send_as(&early, slice::from_ref(&claim)).await, Errors::SaleNotEnded);
+
+    env.warp_to_timestamp(args.end).await;
+
+    let refund = instructions::refund_contribution(late.pubkey(), mint);
+    assert_error(env.send_as(&late, &[refund]).await, Errors::SoftCapMet);
+
+    env.send_as(&early, &[claim]).await.unwrap();
+    assert_eq!(env.token_balance(&ata(&early.pubkey(), &mint)).await, args.token_amount * 2 / 3);
+
+    let close = instructions::close_sale(env.admin.pubkey(), mint);
+    assert_error(env.send(slice::from_ref(&close), &[]).await, Errors::SaleNotSettled);
+
+    env.send_as(&late, &[instructions::claim_sale_tokens(late.pubkey(), mint)]).await.unwrap();
+    assert_eq!(env.token_balance(&ata(&late.pubkey(), &mint)).await, args.token_amount / 3);
+
+    let withdraw = instructions::withdraw_sale_proceeds(env.admin.pubkey(), mint);
+    env.send(slice::from_ref(&withdraw), &[]).await.unwrap();
+    let sale = env.sale(&mint).await.unwrap();
+    assert!(sale.proceeds_withdrawn);
+    assert_eq!(sale.total_raised, 3 * LAMPORTS_PER_SOL / 2);
+    assert_error(env.send(&[withdraw], &[]).await, Errors::SaleProceedsWithdrawn);
+
+    env.send(&[close], &[]).await.unwrap();
+    assert!(env.sale(&mint).await.is_none());
+    assert_eq!(env.project(&mint).await.reserved_tokens, 0);
+}
+
+#[tokio::test]
+async fn presale_refunds_below_soft_cap() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+
+    let contributor = Keypair::new();
+    env.fund(&contributor.pubkey(), 5 * LAMPORTS_PER_SOL).await;
+
+    let args = SaleArgs { end: 0, ..sale_args(env.now().await, [0; 32]) };
+    let ix = instructions::create_sale(env.admin.pubkey(), mint, args);
+    assert_error(env.send(&[ix], &[]).await, Errors::InvalidSaleConfig);
+
+    let start = env.now().await + 60;
+    let args = sale_args(start, [0; 32]);
+    env.send(&[instructions::create_sale(env.admin.pubkey(), mint, args.clone())], &[]).await.unwrap();
+
+    let ix = instructions::contribute(contributor.pubkey(), mint, LAMPORTS_PER_SOL / 2, vec![]);
+    assert_error(env.send_as(&contributor, slice::from_ref(&ix)).await, Errors::SaleNotActive);
+
+    env.warp_to_timestamp(start).await;
+    env.send_as(&contributor, &[ix]).await.unwrap();
+
+    let sale_address = pda::sale(&pda::project(&mint).0).0;
+    let escrowed = env.account(&sale_address).await.unwrap().lamports;
+
+    env.warp_to_timestamp(args.end).await;
+
+    let claim = instructions::claim_sale_tokens(contributor.pubkey(), mint);
+    assert_error(env.send_as(&contributor, &[claim]).await, Errors::SoftCapNotMet);
+
+    env.send_as(&contributor, &[instructions::refund_contribution(contributor.pubkey(), mint)]).await.unwrap();
+    assert_eq!(env.account(&sale_address).await.unwrap().lamports, escrowed - LAMPORTS_PER_SOL / 2);
+
+    env.send(&[instructions::close_sale(env.admin.pubkey(), mint)], &[]).await.unwrap();
+    assert_eq!(env.project(&mint).await.reserved_tokens, 0);
+}
+
+#[tokio::test]
+async fn close_project_waits_for_open_sale() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch(InitProjectArgs {
+        dao_allocation: 0,
+        ..launch_args()
+    }).await.pubkey();
+
+    let args = sale_args(env.now().await, [0; 32]);
+    env.send(&[instructions::create_sale(env.admin.pubkey(), mint, args.clone())], &[]).await.unwrap();
+    env.send(&[
+        instructions::burn_vault_tokens(env.admin.pubkey(), mint, AIRDROP_SUPPLY - args.token_amount),
+        instructions::burn_authority(env.admin.pubkey(), mint)
+    ], &[]).await.unwrap();
+
+    let ix = instructions::close_project(env.admin.pubkey(), mint);
+    assert_error(env.send(slice::from_ref(&ix), &[]).await, Errors::SaleNotClosed);
+
+    env.warp_to_timestamp(args.end).await;
+    env.send(&[
+        instructions::close_sale(env.admin.pubkey(), mint),
+        instructions::burn_vault_tokens(env.admin.pubkey(), mint, args.token_amount)
+    ], &[]).await.unwrap();
+
+    env.send(&[ix], &[]).await.unwrap();
+    assert!(env.account(&pda::project(&mint).0).await.is_none());
+}
+
+#[tokio::test]
+async fn auction_settles_every_bid_at_the_clearing_price() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+
+    let (early, late) = (Keypair::new(), Keypair::new());
+    for wallet in [&early, &late] {
+        env.fund(&wallet.pubkey(), 10 * LAMPORTS_PER_SOL).await;
+    }
+
+    let start = env.now().await;
+    let args = auction_args(start);
+    env.send(&[instructions::create_auction(env.admin.pubkey(), mint, args.clone())], &[]).await.unwrap();
+    assert_eq!(env.project(&mint).await.reserved_tokens, args.token_amount);
+
+    // 2 tokens at the start price of 2 SOL
+    env.send_as(&early, &[instructions::place_bid(early.pubkey(), mint, 2_000_000)]).await.unwrap();
+
+    // halfway through the price is 1.25 SOL, and only 3 tokens are left
+    env.warp_to_timestamp(start + 500).await;
+    env.send_as(&late, &[instructions::place_bid(late.pubkey(), mint, 10_000_000)]).await.unwrap();
+
+    let auction = env.auction(&mint).await.unwrap();
+    assert_eq!(auction.tokens_committed, args.token_amount);
+    assert_eq!(auction.clearing_price, 5 * LAMPORTS_PER_SOL / 4);
+    assert_eq!(auction.total_deposited, 4 * LAMPORTS_PER_SOL + 15 * LAMPORTS_PER_SOL / 4);
+
+    let ix = instructions::place_bid(early.pubkey(), mint, 1);
+    assert_error(env.send_as(&early, &[ix]).await, Errors::AuctionNotActive);
+
+    let auction_address = pda::auction(&pda::project(&mint).0).0;
+    let escrowed = env.account(&auction_address).await.unwrap().lamports;
+
+    env.send_as(&early, &[instructions::claim_auction_tokens(early.pubkey(), mint)]).await.unwrap();
+    assert_eq!(env.token_balance(&ata(&early.pubkey(), &mint)).await, 2_000_000);
+    assert_eq!(env.account(&auction_address).await.unwrap().lamports, escrowed - 3 * LAMPORTS_PER_SOL / 2);
+
+    let ix = instructions::withdraw_auction_proceeds(late.pubkey(), mint, late.pubkey());
+    assert_error(env.send_as(&late, &[ix]).await, Errors::InvalidProceedsDestination);
+
+    let creator_lamports = env.account(&env.admin.pubkey()).await.unwrap().lamports;
+    let ix = instructions::withdraw_auction_proceeds(late.pubkey(), mint, env.admin.pubkey());
+    env.send_as(&late, &[ix]).await.unwrap();
+    assert_eq!(
+        env.account(&env.admin.pubkey()).await.unwrap().lamports,
+        creator_lamports + 25 * LAMPORTS_PER_SOL / 4
+    );
+
+    let close = instructions::close_auction(env.admin.pubkey(), mint);
+    assert_error(env.send(slice::from_ref(&close), &[]).await, Errors::AuctionNotSettled);
+
+    env.send_as(&late, &[instructions::claim_auction_tokens(late.pubkey(), mint)]).await.unwrap();
+    assert_eq!(env.token_balance(&ata(&late.pubkey(), &mint)).await, 3_000_000);
+
+    env.send(&[close], &[]).await.unwrap();
+    assert!(env.auction(&mint).await.is_none());
+    assert_eq!(env.project(&mint).await.reserved_tokens, 0);
+}
+
+#[tokio::test]
+async fn unsold_auction_clears_at_the_floor() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+
+    let bidder = Keypair::new();
+    env.fund(&bidder.pubkey(), 10 * LAMPORTS_PER_SOL).await;
+
+    let start = env.now().await;
+    let args = AuctionArgs { decay_interval: 0, ..auction_args(start) };
+    let ix = instructions::create_auction(env.admin.pubkey(), mint, args);
+    assert_error(env.send(&[ix], &[]).await, Errors::InvalidAuctionConfig);
+
+    let args = auction_args(start);
+    env.send(&[instructions::create_auction(env.admin.pubkey(), mint, args.clone())], &[]).await.unwrap();
+    env.send_as(&bidder, &[instructions::place_bid(bidder.pubkey(), mint, 1_000_000)]).await.unwrap();
+
+    let claim = instructions::claim_auction_tokens(bidder.pubkey(), mint);
+    assert_error(env.send_as(&bidder, slice::from_ref(&claim)).await, Errors::AuctionNotEnded);
+
+    env.warp_to_timestamp(args.end).await;
+
+    let auction_address = pda::auction(&pda::project(&mint).0).0;
+    let escrowed = env.account(&auction_address).await.unwrap().lamports;
+
+    env.send_as(&bidder, &[claim]).await.unwrap();
+    assert_eq!(env.account(&auction_address).await.unwrap().lamports, escrowed - 3 * LAMPORTS_PER_SOL / 2);
+
+    let withdraw = instructions::withdraw_auction_proceeds(env.admin.pubkey(), mint, env.admin.pubkey());
+    env.send(slice::from_ref(&withdraw), &[]).await.unwrap();
+    assert_error(env.send(&[withdraw], &[]).await, Errors::AuctionProceedsWithdrawn);
+
+    env.send(&[instructions::close_auction(env.admin.pubkey(), mint)], &[]).await.unwrap();
+    assert_eq!(env.project(&mint).await.reserved_tokens, 0);
+    assert_eq!(env.vault_balance(&mint).await, AIRDROP_SUPPLY - 1_000_000);
+}
+
+#[tokio::test]
+async fn auction_proceeds_go_to_the_dao_treasury() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch_default().await.pubkey();
+
+    let (result, dao) = env.init_dao(&mint, "Tatami DAO", DAO_ALLOCATION).await;
+    result.unwrap();
+
+    let bidder = Keypair::new();
+    env.fund(&bidder.pubkey(), 10 * LAMPORTS_PER_SOL).await;
+
+    let args = auction_args(env.now().await);
+    env.send(&[instructions::create_auction(env.admin.pubkey(), mint, args.clone())], &[]).await.unwrap();
+    env.send_as(&bidder, &[instructions::place_bid(bidder.pubkey(), mint, 1_000_000)]).await.unwrap();
+    env.warp_to_timestamp(args.end).await;
+
+    let ix = instructions::withdraw_auction_proceeds(bidder.pubkey(), mint, env.admin.pubkey());
+    assert_error(env.send_as(&bidder, &[ix]).await, Errors::InvalidProceedsDestination);
+
+    let treasury_lamports = env.account(&dao.native_treasury).await.map_or(0, |account| account.lamports);
+    let ix = instructions::withdraw_auction_proceeds(bidder.pubkey(), mint, dao.native_treasury);
+    env.send_as(&bidder, &[ix]).await.unwrap();
+    assert_eq!(
+        env.account(&dao.native_treasury).await.unwrap().lamports,
+        treasury_lamports + args.floor_price
+    );
+}
+
+#[tokio::test]
+async fn close_project_waits_for_open_auction() {
+    let mut env = TestEnv::new().await;
+    let mint = env.launch(InitProjectArgs {
+        dao_allocation: 0,
+        ..launch_args()
+    }).await.pubkey();
+
+    let args = auction_args(env.now().await);
+    env.send(&[instructions::create_auction(env.admin.pubkey(), mint, args.clone())], &[]).await.unwrap();
+    env.send(&[
+        instructions::burn_vault_tokens(env.admin.pubkey(), mint, AIRDROP_SUPPLY - args.token_amount),
+        instructions::burn_authority(env.admin.pubkey(), mint)
+    ], &[]).await.unwrap();
+
+    let ix = instructions::close_project(env.admin.pubkey(), mint);
+    assert_error(env.send(slice::from_ref(&ix), &[]).await, Errors::AuctionNotClosed);
+
+    env.warp_to_timestamp(args.end).await;
+    env.send(&[
+        instructions::close_auction(env.admin.pubkey(), mint),
+        instructions::burn_vault_tokens(env.admin.pubkey(), mint, args.token_amount)
+    ], &[]).await.unwrap();
+
+    env.send(&[ix], &[]).await.unwrap();
+    assert!(env.account(&pda::project(&mint).0).await.is_none());
+}
+
+#[tokio::test]
+async fn lp_lock_holds_until_unlock_time() {
+    let mut env = TestEnv::new().await;
+    env.setup_amm().await;
+    let (mint, pool) = env.launch_with_pool(launch_args()).await;
+    let lp_mint = pool.lp_mint;
+    let wallet = ata(&env.admin.pubkey(), &lp_mint);
+    let escrow = ata(&pda::lp_lock(&pda::project(&mint).0).0, &lp_mint);
+
+    let creator = env.admin.pubkey();
+    let unlock_at = env.now().await + 86_400;
+    let lock = |amount, unlock_at| instructions::lock_lp(creator, mint, lp_mint, amount, unlock_at);
+
+    let unseeded = env.launch_default().await.pubkey();
+    let ix = instructions::lock_lp(creator, unseeded, lp_mint, 400_000, unlock_at);
+    assert_error(env.send(&[ix], &[]).await, Errors::LpNotInitialized);
+
+    assert_error(env.send(&[lock(0, unlock_at)], &[]).await, Errors::InvalidAmount);
+    env.send(&[lock(400_000, unlock_at)], &[]).await.unwrap();
+    assert_error(env.send(&[lock(0, unlock_at - 1)], &[]).await, Errors::InvalidUnlockTime);
+    env.send(&[lock(100_000, unlock_at + 60)], &[]).await.unwrap();
+
+    let other_mint = instructions::lock_lp(creator, mint, mint, 1, unlock_at + 60);
+    assert_error(env.send(&[other_mint], &[]).await, Errors::InvalidLpMint);
+
+    let lp_lock = env.lp_lock(&mint).await.unwrap();
+    assert_eq!((lp_lock.amount, lp_lock.unlock_at), (500_000, unlock_at + 60));
+    assert_eq!(env.project(&mint).await.lp_lock, LpLockStatus::Locked);
+    assert_eq!(env.token_balance(&escrow).await, 500_000);
+
+    let unlock = instructions::unlock_lp(env.admin.pubkey(), mint, lp_mint, wallet);
+    assert_error(env.send(slice::from_ref(&unlock), &[]).await, Errors::LpStillLocked);
+
+    env.warp_to_timestamp(unlock_at + 60).await;
+    env.send(&[unlock], &[]).await.unwrap();
+
+    assert_eq!(env.token_balance(&wallet).await, LP_TOKENS);
+    assert!(env.account(&escrow).await.is_none());
+    assert!(env.lp_lock(&mint).await.is_none());
+    assert_eq!(env.project(&mint).await.lp_lock, LpLockStatus::Unlocked);
+}
+
+#[tokio::test]
+async fn permanent_lp_lock_can_only_be_burned() {
+    let mut env = TestEnv::new().await;
+    env.setup_amm().await;
+    let (mint, pool) = env.launch_with_pool(launch_args()).await;
+    let lp_mint = pool.lp_mint;
+    let wallet = ata(&env.admin.pubkey(), &lp_mint);
+
+    let unlock_at = env.now().await + 60;
+    env.send(&[instructions::lock_lp(env.admin.pubkey(), mint, lp_mint, 400_000, unlock_at)], &[]).await.unwrap();
+    env.send(&[instructions::make_lp_lock_permanent(env.admin.pubkey(), mint)], &[]).await.unwrap();
+    assert_eq!(env.project(&mint).await.lp_lock, LpLockStatus::Permanent);
+
+    env.warp_to_timestamp(unlock_at).await;
+    let unlock = instructions::unlock_lp(env.admin.pubkey(), mint, lp_mint, wallet);
+    assert_error(env.send(&[unlock], &[]).await, Errors::LpLockPermanent);
+
+    let supply = env.mint_state(&lp_mint).await.supply;
+    env.send(&[instructions::burn_locked_lp(env.admin.pubkey(), mint, lp_mint)], &[]).await.unwrap();
+    assert_eq!(env.mint_state(&lp_mint).await.supply, supply - 400_000);
+    assert_eq!(env.project(&mint).await.lp_lock, LpLockStatus::Burned);
+    assert!(env.lp_lock(&mint).await.is_none());
+    assert!(env.account(&ata(&pda::lp_lock(&pda::project(&mint).0).0, &lp_mint)).await.is_none());
+
+    let lock = instructions::lock_lp(env.admin.pubkey(), mint, lp_mint, 100_000, unlock_at + 60);
+    assert_error(env.send(&[lock], &[]).await, Errors::LpNotLocked);
+}
+
+#[tokio::test]
+async fn close_project_waits_for_lp_unlock() {
+    let mut env = TestEnv::new().await;
+    env.setup_amm().await;
+    let (mint, pool) = env.launch_with_pool(InitProjectArgs {
+        dao_allocation: 0,
+        ..launch_args()
+    }).await;
+    let lp_mint = pool.lp_mint;
+    let wallet = ata(&env.admin.pubkey(), &lp_mint);
+
+    let unlock_at = env.now().await + 60;
+    env.send(&[
+        instructions::lock_lp(env.admin.pubkey(), mint, lp_mint, 400_000, unlock_at),
+        instructions::burn_vault_tokens(env.admin.pubkey(), mint, AIRDROP_SUPPLY - seed_args().token_amount),
+        instructions::burn_authority(env.admin.pubkey(), mint)
+    ], &[]).await.unwrap();
+
+    let ix = instructions::close_project(env.admin.pubkey(), mint);
+    assert_error(env.send(slice::from_ref(&ix), &[]).await, Errors::LpLockOpen);
+
+    env.warp_to_timestamp(unlock_at).await;
+    env.send(&[instructions::unlock_lp(env.admin.pubkey(), mint, lp_mint, wallet)], &[]).await.unwrap();
+
+    env.send(&[ix], &[]).await.unwrap();
+    assert!(env.account(&pda::project(&mint).0).await.is_none());
+}
+
+#[tokio::test]
+async fn close_project_after_burning_locked_lp() {
+    let mut env = TestEnv::new().await;
+    env.setup_amm().await;
+    let (mint, pool) = env.launch_with_pool(InitProjectArgs {
+        dao_allocation: 0,
+        ..launch_args()
+    }).await;
+    let lp_mint = pool.lp_mint;
+
+    let unlock_at = env.now().await + 60;
+    env.send(&[
+        instructions::lock_lp(env.admin.pubkey(), mint, lp_mint, 400_000, unlock_at),
+        instructions::burn_vault_tokens(env.admin.pubkey(), mint, AIRDROP_SUPPLY - seed_args().token_amount),
+        instructions::burn_authority(env.admin.pubkey(), mint),
+        instructions::burn_locked_lp(env.admin.pubkey(), mint, lp_mint)
+    ], &[]).await.unwrap();
+
+    env.send(&[instructions::close_project(env.admin.pubkey(), mint)], &[]).await.unwrap();
+    assert!(env.account(&pda::project(&mint).0).await.is_none());
+}
+
+fn initialize_lp_ix(creator: Pubkey, mint: Pubkey, amm_accounts: Vec<AccountMeta>) -> Instruction {
+    instructions::initialize_lp(creator, mint, mock_amm::ID, amm_accounts, seed_args())
+}
+
+#[tokio::test]
+async fn initialize_lp_seeds_an_approved_amm() {
+    let mut env = TestEnv::new().await;
+    env.setup_amm().await;
+
+    let creator = env.admin.pubkey();
+    let mint = env.launch(InitProjectArgs { features: FEATURE_DAO | FEATURE_LP, ..launch_args() }).await.pubkey();
+    let quote_mint = env.launch_default().await.pubkey();
+    let pool = CpSwapPool::derive(mock_amm::ID, amm_config(), &mint, &quote_mint);
+    let amm_accounts = || pool.accounts(&creator, Pubkey::new_unique());
+    let budget = ComputeBudgetInstruction::set_compute_unit_limit(400_000);
+
+    let ix = instructions::initialize_lp(creator, mint, anchor_spl::token::ID, amm_accounts(), seed_args());
+    assert_error(env.send(&[budget.clone(), ix], &[]).await, Errors::AmmNotApproved);
+
+    let mut wrong_pool = amm_accounts();
+    wrong_pool[3].pubkey = Pubkey::new_unique();
+    let ix = initialize_lp_ix(creator, mint, wrong_pool);
+    assert_error(env.send(&[budget.clone(), ix], &[]).await, Errors::InvalidAmmAccounts);
+
+    let unpaid = env.launch_default().await.pubkey();
+    let unpaid_pool = CpSwapPool::derive(mock_amm::ID, amm_config(), &unpaid, &quote_mint);
+    let ix = initialize_lp_ix(creator, unpaid, unpaid_pool.accounts(&creator, Pubkey::new_unique()));
+    assert_error(env.send(&[budget.clone(), ix], &[]).await, Errors::FeatureNotPaid);
+
+    env.send(&[budget.clone(), initialize_lp_ix(creator, mint, amm_accounts())], &[]).await.unwrap();
+
+    let lp_account = ata(&creator, &pool.lp_mint);
+    let (mint_vault, quote_vault) = if pool.token_0_mint == mint {
+        (pool.token_0_vault, pool.token_1_vault)
+    } else {
+        (pool.token_1_vault, pool.token_0_vault)
+    };
+
+    let project = env.project(&mint).await;
+    assert!(project.lp_init);
+    assert_eq!((project.lp_mint, project.pool), (pool.lp_mint, pool.pool_state));
+    assert_eq!(env.vault_balance(&mint).await, AIRDROP_SUPPLY - 2_000_000);
+    assert_eq!(env.token_balance(&ata(&creator, &mint)).await, TEAM_SUPPLY);
+    assert_eq!(env.token_balance(&mint_vault).await, 2_000_000);
+    assert_eq!(env.token_balance(&quote_vault).await, 500_000);
+    assert_eq!(env.token_balance(&lp_account).await, LP_TOKENS);
+
+    let ix = initialize_lp_ix(creator, mint, amm_accounts());
+    assert_error(env.send(&[budget, ix], &[]).await, Errors::LpAlreadyInitialized);
+
+    let unlock_at = env.now().await + 86_400;
+    env.send(&[instructions::lock_lp(creator, mint, pool.lp_mint, LP_TOKENS, unlock_at)], &[]).await.unwrap();
+    assert_eq!(env.project(&mint).await.lp_lock, LpLockStatus::Locked);
+}
+
+#[tokio::test]
+async fn amm_approvals_are_capped() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.pubkey();
+    let programs: Vec<Pubkey> = (0..tatami_v2::MAX_AMM_PROGRAMS).map(|_| Pubkey::new_unique()).collect();
+
+    for program in &programs {
+        env.send(&[instructions::approve_amm(admin, *program, AmmKind::CpSwap)], &[]).await.unwrap();
+    }
+    env.send(&[instructions::approve_amm(admin, programs[0], AmmKind::CpSwap)], &[]).await.unwrap();
+
+    let extra = Pubkey::new_unique();
+    let ix = instructions::approve_amm(admin, extra, AmmKind::CpSwap);
+    assert_error(env.send(slice::from_ref(&ix), &[]).await, Errors::TooManyAmmPrograms);
+
+    let revoke = instructions::revoke_amm(admin, extra);
+    assert_error(env.send(&[revoke], &[]).await, Errors::AmmNotApproved);
+
+    env.send(&[instructions::revoke_amm(admin, programs[0])], &[]).await.unwrap();
+    env.send(&[ix], &[]).await.unwrap();
+
+    let config = env.config().await;
+    assert_eq!(config.approved_amm(&programs[0]), None);
+    assert_eq!(config.approved_amm(&extra), Some(AmmKind::CpSwap));
+    assert_eq!(config.amm_count as usize, tatami_v2::MAX_AMM_PROGRAMS);
+}
@@ -0,0 +1,13 @@
+#!/usr/bin/env bash
+# Dumps the external programs the Rust tests run against into
+# programs/tatami-v2/tests/fixtures. The dumps are committed so the suite runs
+# offline; rerun this and commit the result only when bumping them.
+set -euo pipefail
+
+FIXTURES="$(dirname "$0")/../programs/tatami-v2/tests/fixtures"
+CLUSTER="${CLUSTER:-mainnet-beta}"
+
+mkdir -p "$FIXTURES"
+
+solana program dump -u "$CLUSTER" metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s "$FIXTURES/mpl_token_metadata.so"
+solana program dump -u "$CLUSTER" GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw "$FIXTURES/spl_governance.so"
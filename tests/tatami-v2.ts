@@ -93,7 +93,20 @@ describe("tatami-program", () => {
     });
 
     xit("initializes project and create token and DAO", async() => {
-        const tx = await program.methods.initProject(6, "Tatami Coin", "TTM", "", 560, [new BN(568500000), new BN(789562000)])
+        const tx = await program.methods.initProject({
+            decimals: 6,
+            name: "Tatami Coin",
+            symbol: "TTM",
+            uri: "",
+            recipients: 560,
+            supply: [new BN(568500000), new BN(789562000)],
+            daoAllocation: new BN(5000000),
+            airdropDeadline: new BN(0),
+            clawbackPolicy: {creator: {}},
+            features: 1,
+            creators: [],
+            collection: null
+        })
         .accounts({
             config,
             project,
@@ -103,7 +116,18 @@ describe("tatami-program", () => {
             vault,
             teamWallet,
             teamTokenAccount,
-            vaultTokenAccount
+            vaultTokenAccount,
+            feeOverride: null,
+            discountTokenAccount: null,
+            feeMint: null,
+            feeTokenMint: null,
+            payerTokenAccount: null,
+            feeVault: null,
+            referral: null,
+            referrer: null,
+            referrerTokenAccount: null,
+            referralEarnings: null,
+            creatorPermit: null
         })
         .signers([mint])
         .transaction()
@@ -111,6 +135,7 @@ describe("tatami-program", () => {
         const initDaoIx = await program.methods.initializeDao(daoName, new BN(5000000), new BN(1000000), 
         false, 5, 86400)
         .accounts({
+            config,
             mint: mint.publicKey,
             councilMint: null,
             communityTokenHolding,
@@ -142,12 +167,14 @@ describe("tatami-program", () => {
 
         const tx = await program.methods.airdropTokens(new BN(5000000))
         .accounts({
+            config,
             project,
             vault,
             vaultTokenAccount,
             receiver,
             recipientTokenAccount,
-            mint: mint.publicKey
+            mint: mint.publicKey,
+            role: null
         })    
         .rpc();
 